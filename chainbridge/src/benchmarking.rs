@@ -0,0 +1,629 @@
+//! Benchmarks for the chainbridge pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks};
+use frame_system::RawOrigin;
+use sp_std::vec;
+
+const SEED: u32 = 0;
+/// Upper bound of the relayer set size used in the benchmarks
+const MAX_RELAYERS: u32 = 64;
+/// Upper bound of the proposed call size used in the benchmarks
+const MAX_PROPOSAL_SIZE: u32 = 16 * 1024;
+/// Upper bound of the resource method name size used in the benchmarks
+const MAX_METHOD_SIZE: u32 = 256;
+/// Upper bound of the chain name size used in the benchmarks
+const MAX_CHAIN_NAME_SIZE: u32 = 256;
+
+/// A chain ID that differs from the identity of this chain
+fn src_chain<T: Trait>() -> T::ChainId {
+    let id: u32 = T::ChainIdentity::get().into();
+    T::ChainId::try_from(id.wrapping_add(1)).unwrap_or_else(|_| T::ChainId::from(0))
+}
+
+/// Whitelists the source chain and registers a resource for the benchmarked proposals.
+fn setup_chain<T: Trait>() -> Result<(T::ChainId, ResourceId), &'static str> {
+    let src_id = src_chain::<T>();
+    let r_id = derive_wide_resource_id(src_id.into(), b"remark");
+    Module::<T>::whitelist(src_id)?;
+    Module::<T>::register_resource(r_id, remark_resource::<T>(b"System.remark".to_vec()))?;
+    Ok((src_id, r_id))
+}
+
+/// A resource for the proposals made with `make_proposal`
+fn remark_resource<T: Trait>(method: Vec<u8>) -> ResourceOf<T> {
+    let call = make_proposal::<T>(0).encode();
+    Resource {
+        method,
+        kind: ResourceKind::Generic,
+        destinations: vec![],
+        enabled: true,
+        call_index: [call[0], call[1]],
+    }
+}
+
+/// Funds the account and reserves a bond for it. Relayers are bonded even if bonding is
+/// disabled, and with enough to be slashed, so that resolving a proposal takes the slashing path.
+fn bond_relayer<T: Trait>(who: &T::AccountId) -> Result<(), &'static str> {
+    let minimum = T::Currency::minimum_balance();
+    let amount = T::RelayerBond::get().max(minimum.saturating_mul(1_000u32.into()));
+    T::Currency::make_free_balance_be(who, amount.saturating_add(minimum));
+    T::Currency::reserve(who, amount)?;
+    <Bonds<T>>::insert(who, RelayerBond { amount, unlocking_at: None });
+    Ok(())
+}
+
+/// Funds the reward pot with a full proposal reward, so that resolving a proposal rewards the
+/// relayers who voted with the outcome.
+fn fund_reward_pot<T: Trait>() {
+    let balance = T::ProposalReward::get().saturating_add(T::Currency::minimum_balance());
+    T::Currency::make_free_balance_be(&Module::<T>::reward_pot_id(), balance);
+}
+
+/// Registers `r` relayers with DORR results, ordered from the highest VRF output down, so that
+/// the first relayer is always active.
+fn setup_relayers<T: Trait>(r: u32) -> Result<Vec<T::AccountId>, &'static str> {
+    let mut relayers = Vec::new();
+    for i in 0..r {
+        let relayer: T::AccountId = account("relayer", i, SEED);
+        bond_relayer::<T>(&relayer)?;
+        Module::<T>::register_relayer(relayer.clone())?;
+
+        let mut pk = [0u8; 32];
+        pk[..4].copy_from_slice(&i.to_le_bytes());
+        let mut val = [0u8; 32];
+        val[..4].copy_from_slice(&(u32::max_value() - i).to_be_bytes());
+        <dorr::Module<T>>::insert_vrf_result_unchecked(&relayer, pk.to_vec(), val.to_vec());
+
+        relayers.push(relayer);
+    }
+    Ok(relayers)
+}
+
+/// Whether an admin change was applied, or scheduled if the runtime configures an admin delay
+fn admin_action_done<T: Trait>(applied: bool) -> bool {
+    applied || <ScheduledAdminActions<T>>::contains_key(0)
+}
+
+/// Number of the relayers created by `setup_relayers(r)` that are active and eligible to vote
+fn active_count<T: Trait>(r: u32) -> u32 {
+    r.min(T::MaxActiveRelayers::get() as u32)
+}
+
+/// A proposed call with an encoded size of roughly `p` bytes
+fn make_proposal<T: Trait>(p: u32) -> T::Proposal {
+    frame_system::Call::<T>::remark(vec![1u8; p as usize]).into()
+}
+
+/// Commits votes of the given relayers without resolving the proposal.
+fn commit_votes<T: Trait>(
+    relayers: &[T::AccountId],
+    nonce: DepositNonce,
+    src_id: T::ChainId,
+    r_id: ResourceId,
+    proposal: &T::Proposal,
+) -> Result<(), &'static str> {
+    let prop_hash = T::Hashing::hash_of(proposal);
+    for relayer in relayers {
+        Module::<T>::commit_vote(
+            relayer.clone(),
+            nonce,
+            src_id,
+            r_id,
+            prop_hash,
+            Box::new(proposal.clone()),
+            true,
+            &mut None,
+        )?;
+    }
+    Ok(())
+}
+
+/// Votes on `v` new proposals, whose calls have a total encoded size of roughly `p` bytes
+fn batch_votes<T: Trait>(v: u32, p: u32, src_id: T::ChainId, r_id: ResourceId) -> Vec<BatchVoteOf<T>> {
+    (1..=v)
+        .map(|nonce| {
+            let proposal = make_proposal::<T>(p / v);
+            (nonce.into(), src_id, r_id, ProposalOrHash::Proposal(Box::new(proposal)))
+        })
+        .collect()
+}
+
+benchmarks! {
+    _ { }
+
+    set_threshold {
+    }: _(RawOrigin::Root, 2)
+    verify {
+        assert!(admin_action_done::<T>(Module::<T>::relayer_threshold() == 2));
+    }
+
+    set_threshold_fraction {
+        let fraction = Some(Perbill::from_percent(50));
+    }: _(RawOrigin::Root, fraction)
+    verify {
+        assert!(admin_action_done::<T>(Module::<T>::relayer_threshold_fraction() == fraction));
+    }
+
+    set_resource {
+        let m in 0 .. MAX_METHOD_SIZE;
+        let r_id = derive_wide_resource_id(src_chain::<T>().into(), b"remark");
+        let resource = remark_resource::<T>(vec![1u8; m as usize]);
+    }: _(RawOrigin::Root, r_id, resource)
+    verify {
+        assert!(Module::<T>::resource_exists(r_id));
+    }
+
+    remove_resource {
+        let r_id = derive_wide_resource_id(src_chain::<T>().into(), b"remark");
+        Module::<T>::register_resource(r_id, remark_resource::<T>(b"System.remark".to_vec()))?;
+    }: _(RawOrigin::Root, r_id)
+    verify {
+        assert!(!Module::<T>::resource_exists(r_id));
+    }
+
+    whitelist_chain {
+        let id = src_chain::<T>();
+    }: _(RawOrigin::Root, id)
+    verify {
+        assert!(Module::<T>::chain_whitelisted(id));
+    }
+
+    disable_chain {
+        let id = src_chain::<T>();
+        Module::<T>::whitelist(id)?;
+    }: _(RawOrigin::Root, id)
+    verify {
+        assert!(Module::<T>::chain_disabled(id));
+    }
+
+    enable_chain {
+        let id = src_chain::<T>();
+        Module::<T>::whitelist(id)?;
+        Module::<T>::set_chain_disabled(id, true)?;
+    }: _(RawOrigin::Root, id)
+    verify {
+        assert!(Module::<T>::chain_whitelisted(id));
+    }
+
+    add_relayer {
+        let relayer: T::AccountId = account("relayer", 0, SEED);
+        bond_relayer::<T>(&relayer)?;
+    }: _(RawOrigin::Root, relayer.clone())
+    verify {
+        assert!(admin_action_done::<T>(Module::<T>::is_relayer(&relayer)));
+    }
+
+    remove_relayer {
+        let relayer: T::AccountId = account("relayer", 0, SEED);
+        bond_relayer::<T>(&relayer)?;
+        Module::<T>::register_relayer(relayer.clone())?;
+        // Worst case: the relayer's DORR entries are removed as well
+        <dorr::Module<T>>::insert_vrf_result_unchecked(&relayer, vec![1; 32], vec![1; 32]);
+    }: _(RawOrigin::Root, relayer.clone())
+    verify {
+        assert!(admin_action_done::<T>(!Module::<T>::is_relayer(&relayer)));
+    }
+
+    veto_admin_action {
+        let at = <frame_system::Module<T>>::block_number() + One::one();
+        <ScheduledAdminActions<T>>::insert(0, (at, AdminAction::SetThreshold(2)));
+        <AdminActionQueue<T>>::append(at, 0);
+        let origin = T::VetoOrigin::successful_origin();
+    }: { Module::<T>::veto_admin_action(origin, 0)? }
+    verify {
+        assert!(!<ScheduledAdminActions<T>>::contains_key(0));
+    }
+
+    pause {
+        let scope = PauseScope::Resource(derive_wide_resource_id(src_chain::<T>().into(), b"remark"));
+    }: _(RawOrigin::Root, scope.clone())
+    verify {
+        assert!(Module::<T>::paused(scope));
+    }
+
+    unpause {
+        let scope = PauseScope::Resource(derive_wide_resource_id(src_chain::<T>().into(), b"remark"));
+        Module::<T>::set_paused(scope.clone(), true)?;
+    }: _(RawOrigin::Root, scope.clone())
+    verify {
+        assert!(!Module::<T>::paused(scope));
+    }
+
+    set_fee {
+        let dest_id = src_chain::<T>();
+        let fee = T::Currency::minimum_balance();
+    }: _(RawOrigin::Root, dest_id, fee)
+    verify {
+        assert_eq!(Module::<T>::bridge_fee(dest_id), fee);
+    }
+
+    set_chain_config {
+        let c in 0 .. MAX_CHAIN_NAME_SIZE;
+        let (src_id, _) = setup_chain::<T>()?;
+        let config = ChainConfig {
+            name: vec![1u8; c as usize],
+            proposal_lifetime: Some(T::BlockNumber::from(100u32)),
+            address_format: AddressFormat::Length(20),
+        };
+    }: _(RawOrigin::Root, src_id, Some(config.clone()))
+    verify {
+        assert_eq!(Module::<T>::chain_config(src_id), Some(config));
+    }
+
+    set_chain_threshold {
+        let (src_id, _) = setup_chain::<T>()?;
+    }: _(RawOrigin::Root, src_id, Some(2))
+    verify {
+        assert!(admin_action_done::<T>(Module::<T>::chain_threshold(src_id) == Some(2)));
+    }
+
+    set_rate_limit {
+        let (src_id, r_id) = setup_chain::<T>()?;
+        let limit = RateLimit {
+            window: T::BlockNumber::from(100u32),
+            max_amount: Some(U256::from(1_000)),
+            max_count: Some(10),
+        };
+    }: _(RawOrigin::Root, BridgeDirection::Outbound, src_id, r_id, Some(limit.clone()))
+    verify {
+        assert_eq!(Module::<T>::rate_limit((BridgeDirection::Outbound, src_id, r_id)), Some(limit));
+    }
+
+    claim_rewards {
+        let relayer: T::AccountId = account("relayer", 0, SEED);
+        let reward = T::Currency::minimum_balance();
+        T::Currency::make_free_balance_be(&Module::<T>::reward_pot_id(), reward + reward);
+        T::Currency::make_free_balance_be(&relayer, reward);
+        <PendingRewards<T>>::insert(&relayer, reward);
+        <TotalPendingRewards<T>>::put(reward);
+    }: _(RawOrigin::Signed(relayer.clone()))
+    verify {
+        assert!(Module::<T>::pending_rewards(&relayer).is_zero());
+    }
+
+    // Requires bonding to be enabled in the runtime
+    bond {
+        let caller: T::AccountId = account("relayer", 0, SEED);
+        let amount = T::RelayerBond::get();
+        T::Currency::make_free_balance_be(&caller, amount.saturating_add(T::Currency::minimum_balance()));
+    }: _(RawOrigin::Signed(caller.clone()))
+    verify {
+        assert_eq!(Module::<T>::bonds(&caller).map(|b| b.amount), Some(amount));
+    }
+
+    // Unbonding an active relayer, which also removes it from the set
+    unbond {
+        let relayer: T::AccountId = account("relayer", 0, SEED);
+        bond_relayer::<T>(&relayer)?;
+        Module::<T>::register_relayer(relayer.clone())?;
+        <dorr::Module<T>>::insert_vrf_result_unchecked(&relayer, vec![1; 32], vec![1; 32]);
+    }: _(RawOrigin::Signed(relayer.clone()))
+    verify {
+        assert!(Module::<T>::bonds(&relayer).and_then(|b| b.unlocking_at).is_some());
+        assert!(!Module::<T>::is_relayer(&relayer));
+    }
+
+    withdraw_unbonded {
+        let relayer: T::AccountId = account("relayer", 0, SEED);
+        bond_relayer::<T>(&relayer)?;
+        <Bonds<T>>::mutate(&relayer, |bond| {
+            if let Some(bond) = bond {
+                bond.unlocking_at = Some(Zero::zero());
+            }
+        });
+    }: _(RawOrigin::Signed(relayer.clone()))
+    verify {
+        assert!(Module::<T>::bonds(&relayer).is_none());
+    }
+
+    // The last vote in favour, which approves and executes the proposal
+    acknowledge_proposal {
+        let r in 1 .. MAX_RELAYERS;
+        let p in 0 .. MAX_PROPOSAL_SIZE;
+
+        let (src_id, r_id) = setup_chain::<T>()?;
+        let relayers = setup_relayers::<T>(r)?;
+        fund_reward_pot::<T>();
+        let active = active_count::<T>(r);
+        Module::<T>::set_relayer_threshold(active)?;
+        let nonce: DepositNonce = 1;
+        let proposal = make_proposal::<T>(p);
+        commit_votes::<T>(&relayers[1..active as usize], nonce, src_id, r_id, &proposal)?;
+        let caller = relayers[0].clone();
+    }: _(RawOrigin::Signed(caller), nonce, src_id, r_id, ProposalOrHash::Proposal(Box::new(proposal.clone())))
+    verify {
+        let prop_hash = T::Hashing::hash_of(&proposal);
+        let votes = Module::<T>::votes((src_id, nonce), prop_hash).ok_or("proposal is missing")?;
+        assert_eq!(votes.status, ProposalStatus::Executed);
+    }
+
+    // A vote against, which rejects a proposal that all other active relayers voted for
+    reject_proposal {
+        let r in 1 .. MAX_RELAYERS;
+        let p in 0 .. MAX_PROPOSAL_SIZE;
+
+        let (src_id, r_id) = setup_chain::<T>()?;
+        let relayers = setup_relayers::<T>(r)?;
+        fund_reward_pot::<T>();
+        let active = active_count::<T>(r);
+        Module::<T>::set_relayer_threshold(active)?;
+        let nonce: DepositNonce = 1;
+        let proposal = make_proposal::<T>(p);
+        commit_votes::<T>(&relayers[1..active as usize], nonce, src_id, r_id, &proposal)?;
+        let caller = relayers[0].clone();
+    }: _(RawOrigin::Signed(caller), nonce, src_id, r_id, ProposalOrHash::Proposal(Box::new(proposal.clone())))
+    verify {
+        let prop_hash = T::Hashing::hash_of(&proposal);
+        let votes = Module::<T>::votes((src_id, nonce), prop_hash).ok_or("proposal is missing")?;
+        assert_eq!(votes.status, ProposalStatus::Rejected);
+    }
+
+    // Votes in favour of `v` new proposals, whose calls are not executed
+    acknowledge_proposals_batch {
+        let v in 1 .. T::MaxVotesPerBatch::get();
+        let r in 1 .. MAX_RELAYERS;
+        let p in 0 .. MAX_PROPOSAL_SIZE;
+
+        let (src_id, r_id) = setup_chain::<T>()?;
+        let relayers = setup_relayers::<T>(r)?;
+        // Keeps the proposals initiated, the weight of executed calls is charged separately
+        Module::<T>::set_relayer_threshold(active_count::<T>(r) + 1)?;
+        let votes = batch_votes::<T>(v, p, src_id, r_id);
+        let caller = relayers[0].clone();
+    }: _(RawOrigin::Signed(caller.clone()), votes)
+    verify {
+        let prop_hash = T::Hashing::hash_of(&make_proposal::<T>(p / v));
+        let votes = Module::<T>::votes((src_id, DepositNonce::from(v)), prop_hash).ok_or("proposal is missing")?;
+        assert_eq!(votes.votes_for, vec![caller]);
+    }
+
+    // Votes against `v` new proposals
+    reject_proposals_batch {
+        let v in 1 .. T::MaxVotesPerBatch::get();
+        let r in 1 .. MAX_RELAYERS;
+        let p in 0 .. MAX_PROPOSAL_SIZE;
+
+        let (src_id, r_id) = setup_chain::<T>()?;
+        let relayers = setup_relayers::<T>(r)?;
+        Module::<T>::set_relayer_threshold(active_count::<T>(r) + 1)?;
+        let votes = batch_votes::<T>(v, p, src_id, r_id);
+        let caller = relayers[0].clone();
+    }: _(RawOrigin::Signed(caller.clone()), votes)
+    verify {
+        let prop_hash = T::Hashing::hash_of(&make_proposal::<T>(p / v));
+        let votes = Module::<T>::votes((src_id, DepositNonce::from(v)), prop_hash).ok_or("proposal is missing")?;
+        assert_eq!(votes.votes_against, vec![caller]);
+    }
+
+    // Evaluation of a proposal that has reached the threshold
+    eval_vote_state {
+        let r in 1 .. MAX_RELAYERS;
+        let p in 0 .. MAX_PROPOSAL_SIZE;
+
+        let (src_id, r_id) = setup_chain::<T>()?;
+        let relayers = setup_relayers::<T>(r)?;
+        fund_reward_pot::<T>();
+        let active = active_count::<T>(r);
+        Module::<T>::set_relayer_threshold(active)?;
+        let nonce: DepositNonce = 1;
+        let proposal = make_proposal::<T>(p);
+        commit_votes::<T>(&relayers[..active as usize], nonce, src_id, r_id, &proposal)?;
+        let caller: T::AccountId = account("caller", 0, SEED);
+    }: _(RawOrigin::Signed(caller), nonce, src_id, ProposalOrHash::Proposal(Box::new(proposal.clone())))
+    verify {
+        let prop_hash = T::Hashing::hash_of(&proposal);
+        let votes = Module::<T>::votes((src_id, nonce), prop_hash).ok_or("proposal is missing")?;
+        assert_eq!(votes.status, ProposalStatus::Executed);
+    }
+
+    // Pruning of `n` expired proposals that are due in the same block
+    prune_proposals {
+        let n in 1 .. T::MaxPrunedPerBlock::get();
+
+        let (src_id, r_id) = setup_chain::<T>()?;
+        let relayers = setup_relayers::<T>(1)?;
+        let proposal = make_proposal::<T>(0);
+        for nonce in 1..=n {
+            commit_votes::<T>(&relayers, nonce.into(), src_id, r_id, &proposal)?;
+        }
+        let expiry = <frame_system::Module<T>>::block_number() + Module::<T>::proposal_lifetime(src_id);
+        <PruneCursor<T>>::put(expiry);
+    }: { Module::<T>::prune_proposals(expiry); }
+    verify {
+        let prop_hash = T::Hashing::hash_of(&proposal);
+        assert!(!<Votes<T>>::contains_key((src_id, DepositNonce::from(n)), prop_hash));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{new_test_ext, RelayerBond, Test};
+    use frame_support::assert_ok;
+
+    #[test]
+    fn set_threshold() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_set_threshold::<Test>());
+        });
+    }
+
+    #[test]
+    fn set_threshold_fraction() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_set_threshold_fraction::<Test>());
+        });
+    }
+
+    #[test]
+    fn set_resource() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_set_resource::<Test>());
+        });
+    }
+
+    #[test]
+    fn remove_resource() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_remove_resource::<Test>());
+        });
+    }
+
+    #[test]
+    fn whitelist_chain() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_whitelist_chain::<Test>());
+        });
+    }
+
+    #[test]
+    fn disable_chain() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_disable_chain::<Test>());
+        });
+    }
+
+    #[test]
+    fn enable_chain() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_enable_chain::<Test>());
+        });
+    }
+
+    #[test]
+    fn add_relayer() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_add_relayer::<Test>());
+        });
+    }
+
+    #[test]
+    fn remove_relayer() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_remove_relayer::<Test>());
+        });
+    }
+
+    #[test]
+    fn veto_admin_action() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_veto_admin_action::<Test>());
+        });
+    }
+
+    #[test]
+    fn pause() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_pause::<Test>());
+        });
+    }
+
+    #[test]
+    fn unpause() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_unpause::<Test>());
+        });
+    }
+
+    #[test]
+    fn set_fee() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_set_fee::<Test>());
+        });
+    }
+
+    #[test]
+    fn set_chain_config() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_set_chain_config::<Test>());
+        });
+    }
+
+    #[test]
+    fn set_chain_threshold() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_set_chain_threshold::<Test>());
+        });
+    }
+
+    #[test]
+    fn set_rate_limit() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_set_rate_limit::<Test>());
+        });
+    }
+
+    #[test]
+    fn claim_rewards() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_claim_rewards::<Test>());
+        });
+    }
+
+    #[test]
+    fn bond() {
+        new_test_ext().execute_with(|| {
+            RelayerBond::set(100);
+            assert_ok!(test_benchmark_bond::<Test>());
+        });
+    }
+
+    #[test]
+    fn unbond() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_unbond::<Test>());
+        });
+    }
+
+    #[test]
+    fn withdraw_unbonded() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_withdraw_unbonded::<Test>());
+        });
+    }
+
+    #[test]
+    fn acknowledge_proposal() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_acknowledge_proposal::<Test>());
+        });
+    }
+
+    #[test]
+    fn reject_proposal() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_reject_proposal::<Test>());
+        });
+    }
+
+    #[test]
+    fn acknowledge_proposals_batch() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_acknowledge_proposals_batch::<Test>());
+        });
+    }
+
+    #[test]
+    fn reject_proposals_batch() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_reject_proposals_batch::<Test>());
+        });
+    }
+
+    #[test]
+    fn eval_vote_state() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_eval_vote_state::<Test>());
+        });
+    }
+
+    #[test]
+    fn prune_proposals() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_prune_proposals::<Test>());
+        });
+    }
+}
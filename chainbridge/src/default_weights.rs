@@ -0,0 +1,155 @@
+//! Default weights for the chainbridge pallet. See the Weights section of the README.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::weights::{constants::RocksDbWeight as DbWeight, Weight};
+
+impl crate::WeightInfo for () {
+    fn set_threshold() -> Weight {
+        (22_000_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn set_threshold_fraction() -> Weight {
+        (22_000_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn set_resource(m: u32) -> Weight {
+        (21_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(m as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn remove_resource() -> Weight {
+        (19_000_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn whitelist_chain() -> Weight {
+        (28_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn disable_chain() -> Weight {
+        (26_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn enable_chain() -> Weight {
+        (26_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn add_relayer() -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+    }
+    fn remove_relayer() -> Weight {
+        (45_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(4 as Weight))
+            .saturating_add(DbWeight::get().writes(7 as Weight))
+    }
+    fn pause() -> Weight {
+        (25_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn unpause() -> Weight {
+        (25_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn set_fee() -> Weight {
+        (24_000_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn set_chain_config(c: u32) -> Weight {
+        (25_000_000 as Weight)
+            .saturating_add((1_000 as Weight).saturating_mul(c as Weight))
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn set_chain_threshold() -> Weight {
+        (24_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn set_rate_limit() -> Weight {
+        (26_000_000 as Weight).saturating_add(DbWeight::get().writes(2 as Weight))
+    }
+    fn claim_rewards() -> Weight {
+        (75_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(4 as Weight))
+            .saturating_add(DbWeight::get().writes(4 as Weight))
+    }
+    fn bond() -> Weight {
+        (55_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+    }
+    fn unbond() -> Weight {
+        (58_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(5 as Weight))
+            .saturating_add(DbWeight::get().writes(8 as Weight))
+    }
+    fn withdraw_unbonded() -> Weight {
+        (50_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+    }
+    fn veto_admin_action() -> Weight {
+        (23_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn acknowledge_proposal(r: u32, p: u32) -> Weight {
+        (120_000_000 as Weight)
+            .saturating_add((1_100_000 as Weight).saturating_mul(r as Weight))
+            .saturating_add((3_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(DbWeight::get().reads(14 as Weight))
+            .saturating_add(DbWeight::get().reads((3 as Weight).saturating_mul(r as Weight)))
+            .saturating_add(DbWeight::get().writes(7 as Weight))
+            .saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(r as Weight)))
+    }
+    fn reject_proposal(r: u32, p: u32) -> Weight {
+        (115_000_000 as Weight)
+            .saturating_add((1_100_000 as Weight).saturating_mul(r as Weight))
+            .saturating_add((3_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(DbWeight::get().reads(14 as Weight))
+            .saturating_add(DbWeight::get().reads((3 as Weight).saturating_mul(r as Weight)))
+            .saturating_add(DbWeight::get().writes(7 as Weight))
+            .saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(r as Weight)))
+    }
+    fn acknowledge_proposals_batch(v: u32, r: u32, p: u32) -> Weight {
+        (20_000_000 as Weight)
+            .saturating_add((115_000_000 as Weight).saturating_mul(v as Weight))
+            .saturating_add((1_100_000 as Weight).saturating_mul(r as Weight))
+            .saturating_add((3_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().reads((10 as Weight).saturating_mul(v as Weight)))
+            .saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(r as Weight)))
+            .saturating_add(DbWeight::get().writes((6 as Weight).saturating_mul(v as Weight)))
+    }
+    fn reject_proposals_batch(v: u32, r: u32, p: u32) -> Weight {
+        (20_000_000 as Weight)
+            .saturating_add((110_000_000 as Weight).saturating_mul(v as Weight))
+            .saturating_add((1_100_000 as Weight).saturating_mul(r as Weight))
+            .saturating_add((3_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().reads((10 as Weight).saturating_mul(v as Weight)))
+            .saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(r as Weight)))
+            .saturating_add(DbWeight::get().writes((6 as Weight).saturating_mul(v as Weight)))
+    }
+    fn eval_vote_state(r: u32, p: u32) -> Weight {
+        (60_000_000 as Weight)
+            .saturating_add((250_000 as Weight).saturating_mul(r as Weight))
+            .saturating_add((3_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(DbWeight::get().reads(11 as Weight))
+            .saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(r as Weight)))
+            .saturating_add(DbWeight::get().writes(4 as Weight))
+            .saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(r as Weight)))
+    }
+    fn prune_proposals(n: u32) -> Weight {
+        (5_000_000 as Weight)
+            .saturating_add((20_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+            .saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+    }
+}
@@ -4,30 +4,53 @@
 use dorr_pallet as dorr;
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage,
-    dispatch::DispatchResult,
+    dispatch::{DispatchError, DispatchResult, DispatchResultWithPostInfo, PostDispatchInfo},
     ensure,
-    traits::{EnsureOrigin, Get},
-    weights::{GetDispatchInfo, Pays},
+    storage::{generator::StorageDoubleMap as _, with_transaction},
+    traits::{
+        Contains, Currency, EnsureOrigin, ExistenceRequirement, Get, Imbalance, OnUnbalanced,
+        ReservableCurrency,
+    },
+    weights::{DispatchClass, DispatchInfo, GetDispatchInfo, Pays, Weight},
     Parameter,
 };
 
 use frame_system::{self as system, ensure_root, ensure_signed};
 use sp_core::U256;
-use sp_runtime::traits::{AccountIdConversion, Dispatchable};
-use sp_runtime::{ModuleId, RuntimeDebug};
-use sp_std::prelude::*;
+use sp_runtime::traits::{
+    AccountIdConversion, Dispatchable, Hash as HashT, MaybeSerializeDeserialize, Member, One,
+    Saturating, Zero,
+};
+use sp_runtime::{ModuleId, Perbill, RuntimeDebug, TransactionOutcome};
+use sp_std::{convert::TryFrom, prelude::*};
 
-use codec::{Decode, Encode, EncodeLike};
+use codec::{Codec, Decode, Encode, EncodeLike};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+mod default_weights;
+pub mod migrations;
 mod mock;
 mod tests;
 
 const DEFAULT_RELAYER_THRESHOLD: u32 = 1;
+/// Number of buckets the window of a rate limit is divided into
+const RATE_LIMIT_BUCKETS: u32 = 10;
 const MODULE_ID: ModuleId = ModuleId(*b"cb/bridg");
+const REWARD_POT_ID: ModuleId = ModuleId(*b"cb/rewrd");
 
-pub type ChainId = u8;
+/// Identifier of a bridged chain, as configured by the runtime
+pub type ChainIdOf<T> = <T as Trait>::ChainId;
 pub type DepositNonce = u64;
 pub type ResourceId = [u8; 32];
+pub type AdminActionId = u32;
+
+pub type BalanceOf<T> =
+    <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+pub type NegativeImbalanceOf<T> =
+    <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::NegativeImbalance;
 
 /// Helper function to concatenate a chain ID and some bytes to produce a resource ID.
 /// The common format is (31 bytes unique ID + 1 byte chain ID).
@@ -41,19 +64,231 @@ pub fn derive_resource_id(chain: u8, id: &[u8]) -> ResourceId {
     return r_id;
 }
 
+/// Helper function to concatenate a wide chain ID and some bytes to produce a resource ID.
+/// The format is (28 bytes unique ID + 4 bytes big-endian chain ID).
+pub fn derive_wide_resource_id(chain: u32, id: &[u8]) -> ResourceId {
+    let mut r_id: ResourceId = [0; 32];
+    r_id[28..].copy_from_slice(&chain.to_be_bytes()); // last 4 bytes are the chain id
+    let range = sp_std::cmp::min(id.len(), 28); // Use at most 28 bytes
+    r_id[28 - range..28].copy_from_slice(&id[id.len() - range..]); // Left padded
+    r_id
+}
+
+/// Storage layout versions of the pallet
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, RuntimeDebug)]
+enum Releases {
+    /// Maps are keyed with `opaque_blake2_256`, as in the first release of the pallet
+    V1OpaqueKeys,
+    /// Maps are keyed with `blake2_128_concat`, and chain IDs are stored as `u8`
+    V2NarrowChainIds,
+    /// Chain IDs are stored as `u32`
+    V3WideChainIds,
+    /// Proposals are keyed by their source chain and nonce, chain IDs are stored as `u8`
+    V4NarrowNonceKeyedVotes,
+    /// Proposals are keyed by their source chain and nonce, chain IDs are stored as `u32`
+    V5WideNonceKeyedVotes,
+}
+
+impl Default for Releases {
+    fn default() -> Self {
+        Releases::V1OpaqueKeys
+    }
+}
+
+impl Releases {
+    /// Latest layout for the chain IDs of the runtime, which are stored as `u8` or `u32`
+    fn current<T: Trait>() -> Self {
+        if T::ChainId::default().encoded_size() > 1 {
+            Releases::V5WideNonceKeyedVotes
+        } else {
+            Releases::V4NarrowNonceKeyedVotes
+        }
+    }
+
+    /// Whether chain IDs are stored as `u8` with this layout
+    fn has_narrow_chain_ids(self) -> bool {
+        match self {
+            Releases::V3WideChainIds | Releases::V5WideNonceKeyedVotes => false,
+            _ => true,
+        }
+    }
+}
+
+/// Range of the chain IDs used by a bridge, and how they are packed into resource IDs.
+///
+/// Chain IDs past `u8::MAX` also need a `u32` `ChainId` in the runtime.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+pub enum ChainIdFormat {
+    /// Chain IDs up to `u8::MAX`, stored in the last byte of resource IDs as in other
+    /// ChainSafe-style deployments
+    Narrow,
+    /// Chain IDs up to `u32::MAX`, stored in the last 4 bytes of resource IDs
+    Wide,
+}
+
+impl ChainIdFormat {
+    /// Highest chain ID of the format
+    pub fn max_chain_id(&self) -> u32 {
+        match self {
+            ChainIdFormat::Narrow => u8::max_value().into(),
+            ChainIdFormat::Wide => u32::max_value(),
+        }
+    }
+
+    /// Derives a resource ID for the chain, if the chain ID fits into the format
+    pub fn derive_resource_id(&self, chain: u32, id: &[u8]) -> Option<ResourceId> {
+        match self {
+            ChainIdFormat::Narrow if chain <= self.max_chain_id() => {
+                Some(derive_resource_id(chain as u8, id))
+            }
+            ChainIdFormat::Narrow => None,
+            ChainIdFormat::Wide => Some(derive_wide_resource_id(chain, id)),
+        }
+    }
+}
+
+/// A proposed call, either in full or referenced by its hash once its preimage is known.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub enum ProposalOrHash<Proposal, Hash> {
+    Proposal(Box<Proposal>),
+    Hash(Hash),
+}
+
+pub type ProposalOrHashOf<T> =
+    ProposalOrHash<<T as Trait>::Proposal, <T as frame_system::Trait>::Hash>;
+
+/// A vote in a batch: (nonce, src_id, r_id, call)
+pub type BatchVoteOf<T> = (DepositNonce, ChainIdOf<T>, ResourceId, ProposalOrHashOf<T>);
+
+/// Direction of a bridge transfer, as seen from this chain
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum BridgeDirection {
+    /// Proposals for deposits made on other chains
+    Inbound,
+    /// Transfers initiated on this chain
+    Outbound,
+}
+
+/// A part of the bridge that can be paused
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum PauseScope<ChainId> {
+    /// All transfers in both directions
+    Bridge,
+    /// All transfers in one direction
+    Direction(BridgeDirection),
+    /// Transfers from or to a chain
+    Chain(ChainId),
+    /// Transfers of a resource
+    Resource(ResourceId),
+}
+
+pub type PauseScopeOf<T> = PauseScope<ChainIdOf<T>>;
+
+/// Format of recipient addresses on a chain
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum AddressFormat {
+    /// Any address
+    Any,
+    /// Addresses of exactly the given number of bytes, e.g. 20 for EVM chains
+    Length(u32),
+    /// Addresses with a number of bytes within the inclusive range
+    LengthRange(u32, u32),
+}
+
+impl AddressFormat {
+    /// Checks if an address has the format
+    pub fn is_valid(&self, address: &[u8]) -> bool {
+        let len = address.len() as u32;
+        match *self {
+            AddressFormat::Any => true,
+            AddressFormat::Length(l) => len == l,
+            AddressFormat::LengthRange(min, max) => min <= len && len <= max,
+        }
+    }
+}
+
+/// Configuration of a whitelisted chain
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ChainConfig<BlockNumber> {
+    /// Human-readable name of the chain
+    pub name: Vec<u8>,
+    /// Lifetime of proposals from the chain, overriding `ProposalLifetime`
+    pub proposal_lifetime: Option<BlockNumber>,
+    /// Format of recipient addresses of outbound transfers to the chain
+    pub address_format: AddressFormat,
+}
+
+/// Kind of asset transferred with a resource
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ResourceKind {
+    /// Fungible assets with the given number of decimals
+    Fungible { decimals: u8 },
+    /// Non-fungible tokens
+    NonFungible,
+    /// Arbitrary data
+    Generic,
+}
+
+/// A resource registered with the bridge
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Resource<ChainId> {
+    /// Method the bridge software maps the resource to, e.g. `SimpleMsg.write_msg`
+    pub method: Vec<u8>,
+    pub kind: ResourceKind,
+    /// Chains the resource can be transferred to. Empty allows every whitelisted chain.
+    pub destinations: Vec<ChainId>,
+    /// Disabled resources are kept registered, but can't be transferred
+    pub enabled: bool,
+    /// Pallet and call index that proposals for the resource must call
+    pub call_index: [u8; 2],
+}
+
+pub type ResourceOf<T> = Resource<ChainIdOf<T>>;
+
+impl<ChainId: PartialEq> Resource<ChainId> {
+    /// Checks if the resource can be transferred to `dest_id`
+    pub fn allows_destination(&self, dest_id: ChainId) -> bool {
+        self.destinations.is_empty() || self.destinations.contains(&dest_id)
+    }
+
+    /// Checks if an encoded call is a call to the registered pallet and call index
+    pub fn matches_call(&self, call: &[u8]) -> bool {
+        call.get(..2) == Some(&self.call_index[..])
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize))]
 pub enum ProposalStatus {
+    /// Voting is in progress
     Initiated,
+    /// Vote threshold reached, the call is being dispatched
     Approved,
+    /// Voting rejected the proposal
     Rejected,
+    /// The call was dispatched successfully
+    Executed,
+    /// The call was dispatched but returned an error
+    Failed(DispatchError),
 }
 
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize))]
 pub struct ProposalVotes<AccountId, BlockNumber> {
     pub votes_for: Vec<AccountId>,
     pub votes_against: Vec<AccountId>,
     pub status: ProposalStatus,
     pub expiry: BlockNumber,
+    /// Relayers eligible to vote, taken from the active relayer set when the proposal was created
+    pub voters: Vec<AccountId>,
+    /// Resource the proposal was created for
+    pub resource_id: ResourceId,
 }
 
 impl<A: PartialEq, B: PartialOrd + Default> ProposalVotes<A, B> {
@@ -71,11 +306,16 @@ impl<A: PartialEq, B: PartialOrd + Default> ProposalVotes<A, B> {
         }
     }
 
-    /// Returns true if the proposal has been rejected or approved, otherwise false.
+    /// Returns true if the proposal has been rejected, approved or executed, otherwise false.
     fn is_complete(&self) -> bool {
         self.status != ProposalStatus::Initiated
     }
 
+    /// Returns true if `who` was an active relayer when the proposal was created
+    fn is_eligible(&self, who: &A) -> bool {
+        self.voters.contains(who)
+    }
+
     /// Returns true if `who` has voted for or against the proposal
     fn has_voted(&self, who: &A) -> bool {
         self.votes_for.contains(&who) || self.votes_against.contains(&who)
@@ -87,6 +327,70 @@ impl<A: PartialEq, B: PartialOrd + Default> ProposalVotes<A, B> {
     }
 }
 
+/// A change to the relayer set or threshold, applied after the admin delay
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub enum AdminAction<AccountId, ChainId> {
+    /// Set the vote threshold for proposals
+    SetThreshold(u32),
+    /// Add a relayer to the set
+    AddRelayer(AccountId),
+    /// Remove a relayer from the set
+    RemoveRelayer(AccountId),
+    /// Set the vote threshold as a fraction of the active relayers, or clear it to use the
+    /// absolute threshold
+    SetThresholdFraction(Option<Perbill>),
+    /// Set or clear the vote threshold of proposals from a chain, overriding both the relayer
+    /// threshold and its fraction
+    SetChainThreshold(ChainId, Option<u32>),
+}
+
+pub type AdminActionOf<T> = AdminAction<<T as frame_system::Trait>::AccountId, ChainIdOf<T>>;
+
+/// Limit on the transfers of a resource to or from a chain, over a rolling window of blocks
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct RateLimit<BlockNumber> {
+    /// Length of the window in blocks
+    pub window: BlockNumber,
+    /// Maximum total amount of outbound fungible transfers in the window
+    pub max_amount: Option<U256>,
+    /// Maximum number of outbound transfers or approved inbound proposals in the window
+    pub max_count: Option<u32>,
+}
+
+/// Transfers made in a bucket of a rate limit window (bucket start, amount, count)
+pub type RateLimitBucket<BlockNumber> = (BlockNumber, U256, u32);
+
+/// Contents of an outbound transfer
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum DepositPayload {
+    /// Fungible transfer (amount, recipient)
+    Fungible(U256, Vec<u8>),
+    /// Nonfungible transfer (token_id, recipient, metadata)
+    NonFungible(Vec<u8>, Vec<u8>, Vec<u8>),
+    /// Generic transfer (metadata)
+    Generic(Vec<u8>),
+}
+
+/// An outbound transfer kept in storage, so that relayers can recover it without its event
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize))]
+pub struct DepositRecord<BlockNumber> {
+    pub resource_id: ResourceId,
+    pub payload: DepositPayload,
+    /// Block in which the transfer was made
+    pub block: BlockNumber,
+}
+
+/// Funds reserved by a relayer to be part of the relayer set
+#[derive(PartialEq, Eq, Clone, Encode, Decode, Default, RuntimeDebug)]
+pub struct RelayerBond<Balance, BlockNumber> {
+    /// Amount currently reserved
+    pub amount: Balance,
+    /// Block from which the bond can be withdrawn, set once unbonding was requested
+    pub unlocking_at: Option<BlockNumber>,
+}
+
 impl<AccountId, BlockNumber: Default> Default for ProposalVotes<AccountId, BlockNumber> {
     fn default() -> Self {
         Self {
@@ -94,29 +398,135 @@ impl<AccountId, BlockNumber: Default> Default for ProposalVotes<AccountId, Block
             votes_against: vec![],
             status: ProposalStatus::Initiated,
             expiry: BlockNumber::default(),
+            voters: vec![],
+            resource_id: [0; 32],
         }
     }
 }
 
+pub trait WeightInfo {
+    fn set_threshold() -> Weight;
+    fn set_threshold_fraction() -> Weight;
+    fn set_resource(m: u32) -> Weight;
+    fn remove_resource() -> Weight;
+    fn whitelist_chain() -> Weight;
+    fn disable_chain() -> Weight;
+    fn enable_chain() -> Weight;
+    fn add_relayer() -> Weight;
+    fn remove_relayer() -> Weight;
+    fn pause() -> Weight;
+    fn unpause() -> Weight;
+    fn set_fee() -> Weight;
+    fn set_chain_config(c: u32) -> Weight;
+    fn set_chain_threshold() -> Weight;
+    fn set_rate_limit() -> Weight;
+    fn claim_rewards() -> Weight;
+    fn bond() -> Weight;
+    fn unbond() -> Weight;
+    fn withdraw_unbonded() -> Weight;
+    fn veto_admin_action() -> Weight;
+    fn acknowledge_proposal(r: u32, p: u32) -> Weight;
+    fn reject_proposal(r: u32, p: u32) -> Weight;
+    fn acknowledge_proposals_batch(v: u32, r: u32, p: u32) -> Weight;
+    fn reject_proposals_batch(v: u32, r: u32, p: u32) -> Weight;
+    fn eval_vote_state(r: u32, p: u32) -> Weight;
+    fn prune_proposals(n: u32) -> Weight;
+}
+
 pub trait Trait: system::Trait + dorr::Trait {
     type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
     /// Origin used to administer the pallet
     type AdminOrigin: EnsureOrigin<Self::Origin>;
+    /// Origin that can cancel scheduled changes to the relayer set and threshold
+    type VetoOrigin: EnsureOrigin<Self::Origin>;
+    /// Number of blocks after which scheduled changes to the relayer set and threshold are
+    /// applied. Zero applies them immediately.
+    type AdminDelay: Get<Self::BlockNumber>;
     /// Proposed dispatchable call
-    type Proposal: Parameter + Dispatchable<Origin = Self::Origin> + EncodeLike + GetDispatchInfo;
+    type Proposal: Parameter
+        + Dispatchable<Origin = Self::Origin, PostInfo = PostDispatchInfo>
+        + EncodeLike
+        + GetDispatchInfo
+        + From<frame_system::Call<Self>>;
+    /// Identifier of a bridged chain, `u8` or `u32`. A `u8` keeps calls, events and storage
+    /// compatible with other ChainSafe-style deployments. Changing it from `u8` to `u32` widens
+    /// the stored chain IDs on the next runtime upgrade.
+    type ChainId: Parameter
+        + Member
+        + Copy
+        + Default
+        + Ord
+        + MaybeSerializeDeserialize
+        + From<u8>
+        + Into<u32>
+        + TryFrom<u32>;
     /// The identifier for this chain.
     /// This must be unique and must not collide with existing IDs within a set of bridged chains.
-    type ChainId: Get<ChainId>;
+    type ChainIdentity: Get<Self::ChainId>;
+    /// Format of the chain IDs of the bridged chains. `ChainIdFormat::Narrow` keeps chain IDs
+    /// and resource IDs compatible with other ChainSafe-style deployments. Chain IDs past
+    /// `u8::MAX` need `ChainIdFormat::Wide` and a `u32` `ChainId`.
+    type ChainIdFormat: Get<ChainIdFormat>;
 
     type ProposalLifetime: Get<Self::BlockNumber>;
+
+    /// Number of blocks a resolved proposal is kept in storage before it is pruned
+    type ProposalRetention: Get<Self::BlockNumber>;
+
+    /// Maximum amount of pruning work (queue entries and visited blocks) done in a single block
+    type MaxPrunedPerBlock: Get<u32>;
+
+    /// Whether the calls of new proposals are stored, so that later votes can reference them by
+    /// hash. Otherwise every vote must include the full call.
+    type StorePreimages: Get<bool>;
+
+    /// Maximum number of votes a relayer can submit in a single batch
+    type MaxVotesPerBatch: Get<u32>;
+
+    /// Number of the latest outbound deposits kept in storage for each destination chain.
+    /// Zero disables deposit records.
+    type DepositRetention: Get<DepositNonce>;
+
+    /// Currency in which outbound transfer fees, relayer rewards and bonds are paid
+    type Currency: ReservableCurrency<Self::AccountId>;
+
+    /// Amount paid from the reward pot for each resolved proposal, split among the relayers
+    /// who voted with the outcome
+    type ProposalReward: Get<BalanceOf<Self>>;
+
+    /// Amount a relayer must reserve to join the relayer set. Zero disables bonding.
+    type RelayerBond: Get<BalanceOf<Self>>;
+
+    /// Number of blocks a bond stays reserved, and slashable, after unbonding
+    type UnbondingDelay: Get<Self::BlockNumber>;
+
+    /// Fraction of its bond a relayer loses for voting against the outcome of a proposal
+    type SlashFraction: Get<Perbill>;
+
+    /// Handler for the funds slashed from relayer bonds
+    type OnSlash: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+    /// Weight information for extrinsics in this pallet
+    type WeightInfo: WeightInfo;
 }
 
 decl_event! {
-    pub enum Event<T> where <T as frame_system::Trait>::AccountId {
+    pub enum Event<T> where
+        <T as frame_system::Trait>::AccountId,
+        <T as frame_system::Trait>::BlockNumber,
+        Balance = BalanceOf<T>,
+        ChainId = ChainIdOf<T>,
+    {
         /// Vote threshold has changed (new_threshold)
         RelayerThresholdChanged(u32),
+        /// Vote threshold as a fraction of the active relayers has changed (new_fraction)
+        RelayerThresholdFractionChanged(Option<Perbill>),
         /// Chain now available for transfers (chain_id)
         ChainWhitelisted(ChainId),
+        /// Chain ID has been disabled
+        ChainDisabled(ChainId),
+        /// Disabled chain ID has been enabled again
+        ChainEnabled(ChainId),
         /// Relayer added to set
         RelayerAdded(AccountId),
         /// Relayer removed from set
@@ -137,8 +547,42 @@ decl_event! {
         ProposalRejected(ChainId, DepositNonce),
         /// Execution of call succeeded
         ProposalSucceeded(ChainId, DepositNonce),
-        /// Execution of call failed
-        ProposalFailed(ChainId, DepositNonce),
+        /// Execution of call failed (src_id, nonce, error)
+        ProposalFailed(ChainId, DepositNonce, DispatchError),
+        /// Resolved or expired proposal removed from storage
+        ProposalPruned(ChainId, DepositNonce),
+        /// Transfers within the scope are halted
+        Paused(PauseScope<ChainId>),
+        /// Transfers within the scope are resumed
+        Unpaused(PauseScope<ChainId>),
+        /// Fee for outbound transfers to a chain has changed (dest_id, fee)
+        FeeChanged(ChainId, Balance),
+        /// Relayers who voted with the outcome of a proposal were rewarded (src_id, nonce, reward per relayer)
+        RelayersRewarded(ChainId, DepositNonce, Balance),
+        /// Relayer claimed its accrued rewards
+        RewardsClaimed(AccountId, Balance),
+        /// Funds reserved towards a relayer bond (who, amount)
+        Bonded(AccountId, Balance),
+        /// Relayer started unbonding, its bond can be withdrawn from the given block
+        Unbonding(AccountId, BlockNumber),
+        /// Relayer bond released after unbonding (who, amount)
+        Withdrawn(AccountId, Balance),
+        /// Relayer bond slashed for voting against the outcome of a proposal (who, amount)
+        RelayerSlashed(AccountId, Balance),
+        /// Admin change scheduled (id, action, block at which it applies)
+        AdminActionScheduled(AdminActionId, AdminAction<AccountId, ChainId>, BlockNumber),
+        /// Scheduled admin change cancelled by the veto origin
+        AdminActionVetoed(AdminActionId),
+        /// Scheduled admin change applied (id, result)
+        AdminActionApplied(AdminActionId, DispatchResult),
+        /// Outcome of a vote submitted in a batch (source_id, deposit_nonce, result)
+        BatchVoteProcessed(ChainId, DepositNonce, DispatchResult),
+        /// Configuration of a chain has changed
+        ChainConfigChanged(ChainId, Option<ChainConfig<BlockNumber>>),
+        /// Vote threshold override of a chain has changed
+        ChainThresholdChanged(ChainId, Option<u32>),
+        /// Rate limit of a resource to or from a chain has changed
+        RateLimitChanged(BridgeDirection, ChainId, ResourceId, Option<RateLimit<BlockNumber>>),
     }
 }
 
@@ -154,13 +598,28 @@ decl_error! {
         ChainNotWhitelisted,
         /// Chain has already been enabled
         ChainAlreadyWhitelisted,
+        /// Chain has been disabled
+        ChainDisabled,
+        /// Chain is already disabled
+        ChainAlreadyDisabled,
+        /// Chain isn't disabled
+        ChainNotDisabled,
         /// Resource ID provided isn't mapped to anything
         ResourceDoesNotExist,
+        /// Resource is registered, but disabled
+        ResourceDisabled,
+        /// Transfer is of a different kind than the resource
+        ResourceKindMismatch,
+        /// Resource can't be transferred to the destination chain
+        DestinationNotAllowed,
+        /// Proposed call doesn't match the call registered for the resource
+        CallMismatch,
         /// Relayer already in set
         RelayerAlreadyExists,
         /// Provided accountId is not a relayer
         RelayerInvalid,
-        /// Protected operation, must be performed by the active relayer
+        /// Protected operation, must be performed by a relayer that was active when the proposal
+        /// was created
         MustBeActiveRelayer,
         /// Protected operation, must be performed by relayer
         MustBeRelayer,
@@ -176,32 +635,198 @@ decl_error! {
         ProposalAlreadyComplete,
         /// Lifetime of proposal has been exceeded
         ProposalExpired,
+        /// Proposal was referenced by hash, but its call is not known
+        ProposalPreimageMissing,
+        /// A proposal for this deposit nonce has already been executed
+        NonceAlreadyExecuted,
+        /// The whole bridge is paused
+        BridgePaused,
+        /// Transfers in this direction are paused
+        DirectionPaused,
+        /// Transfers from or to this chain are paused
+        ChainPaused,
+        /// Transfers of this resource are paused
+        ResourcePaused,
+        /// The scope is already paused
+        AlreadyPaused,
+        /// The scope is not paused
+        NotPaused,
+        /// There are no rewards to claim
+        NoRewards,
+        /// The reward pot has no funds above the existential deposit to pay out
+        RewardPotExhausted,
+        /// The payout is below the existential deposit and can't create the account
+        RewardBelowExistentialDeposit,
+        /// Relayer bonds are not required
+        BondingDisabled,
+        /// The bond already covers the required amount
+        AlreadyBonded,
+        /// Relayer has not reserved the required bond
+        InsufficientBond,
+        /// Account has no bond
+        NotBonded,
+        /// Unbonding was already requested
+        AlreadyUnbonding,
+        /// Unbonding was not requested
+        NotUnbonding,
+        /// The unbonding delay has not passed yet
+        BondStillLocked,
+        /// No admin change with this ID is scheduled
+        AdminActionNotScheduled,
+        /// Too many votes in a single batch
+        TooManyVotes,
+        /// Chain configuration has a zero proposal lifetime or threshold, or an empty address
+        /// length range
+        InvalidChainConfig,
+        /// Recipient address doesn't have the format of the destination chain
+        InvalidRecipient,
+        /// Rate limit window cannot be 0
+        InvalidRateLimit,
+        /// Transfer would exceed the rate limit of the resource and chain
+        RateLimitExceeded,
+        /// Vote references a different resource than the proposal was created with
+        ResourceMismatch,
     }
 }
 
 decl_storage! {
     trait Store for Module<T: Trait> as ChainBridge {
         /// All whitelisted chains and their respective transaction counts
-        ChainNonces get(fn chains): map hasher(opaque_blake2_256) ChainId => Option<DepositNonce>;
+        ChainNonces get(fn chains): map hasher(blake2_128_concat) T::ChainId => Option<DepositNonce>;
 
         /// Number of votes required for a proposal to execute
         RelayerThreshold get(fn relayer_threshold): u32 = DEFAULT_RELAYER_THRESHOLD;
 
+        /// Fraction of the active relayers whose votes are required for a proposal to execute.
+        /// Takes precedence over `RelayerThreshold` when set.
+        RelayerThresholdFraction get(fn relayer_threshold_fraction): Option<Perbill>;
+
         /// Tracks current relayer set
-        pub Relayers get(fn relayers): map hasher(opaque_blake2_256) T::AccountId => bool;
+        pub Relayers get(fn relayers): map hasher(blake2_128_concat) T::AccountId => bool;
 
         /// Number of relayers in set
         pub RelayerCount get(fn relayer_count): u32;
 
         /// All known proposals.
-        /// The key is the hash of the call and the deposit ID, to ensure it's unique.
+        /// Keyed by the source chain and deposit nonce, then by the hash of the call, so that
+        /// the proposals of a deposit are read without those of other deposits.
         pub Votes get(fn votes):
-            double_map hasher(opaque_blake2_256) ChainId, hasher(opaque_blake2_256) (DepositNonce, T::Proposal)
+            double_map hasher(blake2_128_concat) (T::ChainId, DepositNonce), hasher(blake2_128_concat) T::Hash
             => Option<ProposalVotes<T::AccountId, T::BlockNumber>>;
 
-        /// Utilized by the bridge software to map resource IDs to actual methods
+        /// Calls of known proposals, stored when the proposal is created if `StorePreimages` is
+        /// set, so that later votes can reference the call by its hash.
+        pub ProposalPreimages get(fn proposal_preimage):
+            double_map hasher(blake2_128_concat) (T::ChainId, DepositNonce), hasher(blake2_128_concat) T::Hash
+            => Option<T::Proposal>;
+
+        /// Registered resources, utilized by the bridge software to map resource IDs to actual methods
         pub Resources get(fn resources):
-            map hasher(opaque_blake2_256) ResourceId => Option<Vec<u8>>
+            map hasher(blake2_128_concat) ResourceId => Option<ResourceOf<T>>;
+
+        /// Highest inbound deposit nonce per source chain, up to which all nonces have been executed.
+        /// Deposit nonces start at 1.
+        pub ExecutedNonceWatermark get(fn executed_nonce_watermark):
+            map hasher(blake2_128_concat) T::ChainId => DepositNonce;
+
+        /// Executed inbound deposit nonces above the watermark of their source chain
+        pub ExecutedNonces get(fn executed_nonces):
+            double_map hasher(blake2_128_concat) T::ChainId, hasher(twox_64_concat) DepositNonce => bool;
+
+        /// Parts of the bridge that are currently paused
+        pub Paused get(fn paused): map hasher(blake2_128_concat) PauseScopeOf<T> => bool;
+
+        /// Proposals scheduled for removal, keyed by the block from which they may be pruned.
+        /// Each proposal has exactly one entry: its expiry while initiated, and the end of its
+        /// retention period once resolved.
+        pub PruneQueue get(fn prune_queue):
+            map hasher(twox_64_concat) T::BlockNumber => Vec<(T::ChainId, DepositNonce, T::Hash)>;
+
+        /// First block of `PruneQueue` that has not been fully processed yet.
+        /// Zero until the first proposal is scheduled.
+        pub PruneCursor get(fn prune_cursor): T::BlockNumber;
+
+        /// Whitelisted chains that have been disabled. Their deposit nonces are kept, so that
+        /// they continue where they left off once the chain is enabled again.
+        pub DisabledChains get(fn chain_disabled):
+            map hasher(blake2_128_concat) T::ChainId => bool;
+
+        /// Configuration of whitelisted chains. Chains without one use the bridge-wide settings.
+        pub ChainConfigs get(fn chain_config):
+            map hasher(blake2_128_concat) T::ChainId => Option<ChainConfig<T::BlockNumber>>;
+
+        /// Number of votes required to approve proposals from a chain, overriding both the
+        /// relayer threshold and its fraction
+        pub ChainThresholds get(fn chain_threshold): map hasher(blake2_128_concat) T::ChainId => Option<u32>;
+
+        /// Rate limits of transfers by direction, chain and resource
+        pub RateLimits get(fn rate_limit):
+            map hasher(blake2_128_concat) (BridgeDirection, T::ChainId, ResourceId)
+            => Option<RateLimit<T::BlockNumber>>;
+
+        /// Transfers counted against each rate limit, by bucket of its window
+        pub RateLimitUsage get(fn rate_limit_usage):
+            map hasher(blake2_128_concat) (BridgeDirection, T::ChainId, ResourceId)
+            => Vec<RateLimitBucket<T::BlockNumber>>;
+
+        /// The latest `DepositRetention` outbound deposits of each destination chain
+        pub DepositRecords get(fn deposit_record):
+            double_map hasher(blake2_128_concat) T::ChainId, hasher(twox_64_concat) DepositNonce
+            => Option<DepositRecord<T::BlockNumber>>;
+
+        /// Fee charged on outbound transfers to each destination chain
+        pub BridgeFees get(fn bridge_fee): map hasher(blake2_128_concat) T::ChainId => BalanceOf<T>;
+
+        /// Rewards accrued by relayers that have not been claimed yet
+        pub PendingRewards get(fn pending_rewards):
+            map hasher(blake2_128_concat) T::AccountId => BalanceOf<T>;
+
+        /// Sum of all pending rewards, which are kept aside in the reward pot
+        pub TotalPendingRewards get(fn total_pending_rewards): BalanceOf<T>;
+
+        /// Bonds reserved by relayers and candidates
+        pub Bonds get(fn bonds):
+            map hasher(blake2_128_concat) T::AccountId => Option<RelayerBond<BalanceOf<T>, T::BlockNumber>>;
+
+        /// Admin changes waiting for their delay to pass, with the block at which they apply
+        pub ScheduledAdminActions get(fn scheduled_admin_action):
+            map hasher(twox_64_concat) AdminActionId => Option<(T::BlockNumber, AdminActionOf<T>)>;
+
+        /// IDs of the admin changes to apply at each block
+        pub AdminActionQueue get(fn admin_action_queue):
+            map hasher(twox_64_concat) T::BlockNumber => Vec<AdminActionId>;
+
+        /// ID of the next scheduled admin change
+        pub NextAdminActionId get(fn next_admin_action_id): AdminActionId;
+
+        /// Storage layout version, used to run storage migrations. New chains start with the
+        /// latest layout.
+        StorageVersion build(|_: &GenesisConfig| Releases::current::<T>()): Releases;
+    }
+}
+
+sp_api::decl_runtime_apis! {
+    pub trait ChainBridgeRuntimeApi<AccountId, BlockNumber, Hash, ChainId> where
+        AccountId: Codec,
+        BlockNumber: Codec,
+        Hash: Codec,
+        ChainId: Codec,
+    {
+        fn is_paused(scope: PauseScope<ChainId>) -> bool;
+        fn get_paused_scopes() -> Vec<PauseScope<ChainId>>;
+        fn get_chains() -> Vec<(ChainId, DepositNonce)>;
+        fn get_chain_config(id: ChainId) -> Option<ChainConfig<BlockNumber>>;
+        fn get_relayers() -> Vec<AccountId>;
+        fn get_relayer_threshold(src_id: ChainId) -> u32;
+        fn get_resources() -> Vec<(ResourceId, Resource<ChainId>)>;
+        fn get_proposals(
+            src_id: ChainId,
+            nonce: DepositNonce,
+            start: Option<Hash>,
+            limit: u32,
+        ) -> Vec<(Hash, ProposalVotes<AccountId, BlockNumber>)>;
+        fn get_deposit(dest_id: ChainId, nonce: DepositNonce) -> Option<DepositRecord<BlockNumber>>;
+        fn get_deposits(dest_id: ChainId, from: DepositNonce, count: u32) -> Vec<(DepositNonce, DepositRecord<BlockNumber>)>;
     }
 }
 
@@ -209,35 +834,69 @@ decl_module! {
     pub struct Module<T: Trait> for enum Call where origin: T::Origin {
         type Error = Error<T>;
 
-        const ChainIdentity: ChainId = T::ChainId::get();
+        const ChainIdentity: T::ChainId = T::ChainIdentity::get();
+        const ChainIdFormat: ChainIdFormat = T::ChainIdFormat::get();
         const ProposalLifetime: T::BlockNumber = T::ProposalLifetime::get();
+        const ProposalRetention: T::BlockNumber = T::ProposalRetention::get();
+        const MaxPrunedPerBlock: u32 = T::MaxPrunedPerBlock::get();
+        const StorePreimages: bool = T::StorePreimages::get();
+        const MaxVotesPerBatch: u32 = T::MaxVotesPerBatch::get();
+        const DepositRetention: DepositNonce = T::DepositRetention::get();
         const BridgeAccountId: T::AccountId = MODULE_ID.into_account();
+        const RewardPotAccountId: T::AccountId = REWARD_POT_ID.into_account();
+        const ProposalReward: BalanceOf<T> = T::ProposalReward::get();
+        const UnbondingDelay: T::BlockNumber = T::UnbondingDelay::get();
+        const SlashFraction: Perbill = T::SlashFraction::get();
+        const AdminDelay: T::BlockNumber = T::AdminDelay::get();
 
         fn deposit_event() = default;
 
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            Self::apply_admin_actions(now).saturating_add(Self::prune_proposals(now))
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            migrations::migrate::<T>()
+        }
+
         /// Sets the vote threshold for proposals.
         ///
         /// This threshold is used to determine how many votes are required
-        /// before a proposal is executed.
+        /// before a proposal is executed. The change is applied after the admin delay.
         ///
         /// # <weight>
         /// - O(1) lookup and insert
         /// # </weight>
-        #[weight = 195_000_000]
+        #[weight = <T as Trait>::WeightInfo::set_threshold()]
         pub fn set_threshold(origin, threshold: u32) -> DispatchResult {
             Self::ensure_admin(origin)?;
-            Self::set_relayer_threshold(threshold)
+            Self::schedule_admin_action(AdminAction::SetThreshold(threshold))
         }
 
-        /// Stores a method name on chain under an associated resource ID.
+        /// Sets the vote threshold as a fraction of the active relayer set, rounded up.
+        ///
+        /// While set, the threshold of each proposal is this fraction of the relayers eligible to
+        /// vote on it, instead of the absolute threshold. `None` switches back to the absolute
+        /// threshold. The change is applied after the admin delay.
         ///
         /// # <weight>
         /// - O(1) write
         /// # </weight>
-        #[weight = 195_000_000]
-        pub fn set_resource(origin, id: ResourceId, method: Vec<u8>) -> DispatchResult {
+        #[weight = <T as Trait>::WeightInfo::set_threshold_fraction()]
+        pub fn set_threshold_fraction(origin, fraction: Option<Perbill>) -> DispatchResult {
             Self::ensure_admin(origin)?;
-            Self::register_resource(id, method)
+            Self::schedule_admin_action(AdminAction::SetThresholdFraction(fraction))
+        }
+
+        /// Registers a resource, or replaces the registered one, under a resource ID.
+        ///
+        /// # <weight>
+        /// - O(1) write
+        /// # </weight>
+        #[weight = <T as Trait>::WeightInfo::set_resource(resource.encode().len() as u32)]
+        pub fn set_resource(origin, id: ResourceId, resource: ResourceOf<T>) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::register_resource(id, resource)
         }
 
         /// Removes a resource ID from the resource mapping.
@@ -248,7 +907,7 @@ decl_module! {
         /// # <weight>
         /// - O(1) removal
         /// # </weight>
-        #[weight = 195_000_000]
+        #[weight = <T as Trait>::WeightInfo::remove_resource()]
         pub fn remove_resource(origin, id: ResourceId) -> DispatchResult {
             Self::ensure_admin(origin)?;
             Self::unregister_resource(id)
@@ -259,82 +918,391 @@ decl_module! {
         /// # <weight>
         /// - O(1) lookup and insert
         /// # </weight>
-        #[weight = 195_000_000]
-        pub fn whitelist_chain(origin, id: ChainId) -> DispatchResult {
+        #[weight = <T as Trait>::WeightInfo::whitelist_chain()]
+        pub fn whitelist_chain(origin, id: T::ChainId) -> DispatchResult {
             Self::ensure_admin(origin)?;
             Self::whitelist(id)
         }
 
-        /// Adds a new relayer to the relayer set.
+        /// Disables a whitelisted chain. Transfers to the chain and proposals from it are rejected
+        /// until it's enabled again, while its deposit nonce is kept.
         ///
         /// # <weight>
         /// - O(1) lookup and insert
         /// # </weight>
-        #[weight = 195_000_000]
+        #[weight = <T as Trait>::WeightInfo::disable_chain()]
+        pub fn disable_chain(origin, id: T::ChainId) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::set_chain_disabled(id, true)
+        }
+
+        /// Enables a disabled chain again, continuing from its last deposit nonce.
+        ///
+        /// # <weight>
+        /// - O(1) lookup and removal
+        /// # </weight>
+        #[weight = <T as Trait>::WeightInfo::enable_chain()]
+        pub fn enable_chain(origin, id: T::ChainId) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::set_chain_disabled(id, false)
+        }
+
+        /// Sets or removes the configuration of a whitelisted chain.
+        ///
+        /// # <weight>
+        /// - O(1) lookup and write
+        /// # </weight>
+        #[weight = <T as Trait>::WeightInfo::set_chain_config(config.encode().len() as u32)]
+        pub fn set_chain_config(
+            origin,
+            id: T::ChainId,
+            config: Option<ChainConfig<T::BlockNumber>>,
+        ) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::configure_chain(id, config)
+        }
+
+        /// Sets or clears the vote threshold of proposals from a whitelisted chain, overriding
+        /// the bridge-wide threshold. The change is applied after the admin delay.
+        ///
+        /// # <weight>
+        /// - O(1) lookup and write
+        /// # </weight>
+        #[weight = <T as Trait>::WeightInfo::set_chain_threshold()]
+        pub fn set_chain_threshold(origin, id: T::ChainId, threshold: Option<u32>) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::schedule_admin_action(AdminAction::SetChainThreshold(id, threshold))
+        }
+
+        /// Adds a new relayer to the relayer set after the admin delay.
+        ///
+        /// # <weight>
+        /// - O(1) lookup and insert
+        /// # </weight>
+        #[weight = <T as Trait>::WeightInfo::add_relayer()]
         pub fn add_relayer(origin, v: T::AccountId) -> DispatchResult {
             Self::ensure_admin(origin)?;
-            Self::register_relayer(v)
+            Self::schedule_admin_action(AdminAction::AddRelayer(v))
         }
 
-        /// Removes an existing relayer from the set.
+        /// Removes an existing relayer from the set after the admin delay.
         ///
         /// # <weight>
         /// - O(1) lookup and removal
         /// # </weight>
-        #[weight = 195_000_000]
+        #[weight = <T as Trait>::WeightInfo::remove_relayer()]
         pub fn remove_relayer(origin, v: T::AccountId) -> DispatchResult {
             Self::ensure_admin(origin)?;
-            Self::unregister_relayer(v)
+            Self::schedule_admin_action(AdminAction::RemoveRelayer(v))
+        }
+
+        /// Cancels a scheduled change to the relayer set or threshold.
+        ///
+        /// # <weight>
+        /// - O(1) lookup and removal
+        /// # </weight>
+        #[weight = <T as Trait>::WeightInfo::veto_admin_action()]
+        pub fn veto_admin_action(origin, id: AdminActionId) -> DispatchResult {
+            T::VetoOrigin::ensure_origin(origin)?;
+            ensure!(
+                <ScheduledAdminActions<T>>::contains_key(id),
+                Error::<T>::AdminActionNotScheduled
+            );
+            <ScheduledAdminActions<T>>::remove(id);
+            Self::deposit_event(RawEvent::AdminActionVetoed(id));
+            Ok(())
+        }
+
+        /// Halts all transfers within the given scope.
+        ///
+        /// # <weight>
+        /// - O(1) lookup and insert
+        /// # </weight>
+        #[weight = <T as Trait>::WeightInfo::pause()]
+        pub fn pause(origin, scope: PauseScopeOf<T>) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::set_paused(scope, true)
+        }
+
+        /// Resumes transfers within the given scope.
+        ///
+        /// # <weight>
+        /// - O(1) lookup and removal
+        /// # </weight>
+        #[weight = <T as Trait>::WeightInfo::unpause()]
+        pub fn unpause(origin, scope: PauseScopeOf<T>) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::set_paused(scope, false)
+        }
+
+        /// Sets the fee charged on outbound transfers to a chain. A zero fee disables it.
+        ///
+        /// # <weight>
+        /// - O(1) write
+        /// # </weight>
+        #[weight = <T as Trait>::WeightInfo::set_fee()]
+        pub fn set_fee(origin, dest_id: T::ChainId, fee: BalanceOf<T>) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::set_bridge_fee(dest_id, fee)
+        }
+
+        /// Sets or removes the rate limit of transfers of a resource to or from a chain.
+        ///
+        /// Outbound, the limit caps the number of transfers and the total amount of fungible
+        /// transfers. Inbound, it caps the number of approved proposals.
+        ///
+        /// # <weight>
+        /// - O(1) write
+        /// # </weight>
+        #[weight = <T as Trait>::WeightInfo::set_rate_limit()]
+        pub fn set_rate_limit(
+            origin,
+            direction: BridgeDirection,
+            chain_id: T::ChainId,
+            resource_id: ResourceId,
+            limit: Option<RateLimit<T::BlockNumber>>,
+        ) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::set_resource_rate_limit(direction, chain_id, resource_id, limit)
+        }
+
+        /// Pays out the rewards accrued by the caller from the reward pot.
+        ///
+        /// The payout is clamped to the free balance of the pot above the existential deposit,
+        /// and whatever can't be paid stays pending. A payout below the existential deposit can
+        /// only be claimed by an account that already exists.
+        ///
+        /// # <weight>
+        /// - O(1) lookup and transfer
+        /// # </weight>
+        #[weight = <T as Trait>::WeightInfo::claim_rewards()]
+        pub fn claim_rewards(origin) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let reward = Self::pending_rewards(&who);
+            ensure!(!reward.is_zero(), Error::<T>::NoRewards);
+
+            let pot = Self::reward_pot_id();
+            let available = T::Currency::free_balance(&pot).saturating_sub(T::Currency::minimum_balance());
+            let payout = sp_std::cmp::min(reward, available);
+            ensure!(!payout.is_zero(), Error::<T>::RewardPotExhausted);
+            ensure!(
+                payout >= T::Currency::minimum_balance() || !T::Currency::total_balance(&who).is_zero(),
+                Error::<T>::RewardBelowExistentialDeposit
+            );
+
+            T::Currency::transfer(&pot, &who, payout, ExistenceRequirement::KeepAlive)?;
+            let remaining = reward - payout;
+            if remaining.is_zero() {
+                <PendingRewards<T>>::remove(&who);
+            } else {
+                <PendingRewards<T>>::insert(&who, remaining);
+            }
+            <TotalPendingRewards<T>>::mutate(|total| *total = total.saturating_sub(payout));
+
+            Self::deposit_event(RawEvent::RewardsClaimed(who, payout));
+            Ok(())
+        }
+
+        /// Reserves the relayer bond, or tops up a slashed one, which is required before the
+        /// caller can be added to the relayer set.
+        ///
+        /// # <weight>
+        /// - O(1) lookup and reserve
+        /// # </weight>
+        #[weight = <T as Trait>::WeightInfo::bond()]
+        pub fn bond(origin) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let required = T::RelayerBond::get();
+            ensure!(!required.is_zero(), Error::<T>::BondingDisabled);
+
+            let mut bond = Self::bonds(&who).unwrap_or_default();
+            ensure!(bond.unlocking_at.is_none(), Error::<T>::AlreadyUnbonding);
+            let missing = required.saturating_sub(bond.amount);
+            ensure!(!missing.is_zero(), Error::<T>::AlreadyBonded);
+
+            T::Currency::reserve(&who, missing)?;
+            bond.amount = required;
+            <Bonds<T>>::insert(&who, bond);
+
+            Self::deposit_event(RawEvent::Bonded(who, missing));
+            Ok(())
+        }
+
+        /// Starts the unbonding delay, after which the bond can be withdrawn. A relayer leaves the
+        /// relayer set right away, the bond stays reserved until `UnbondingDelay` has passed so
+        /// that it can still be slashed. Unbonding follows its own schedule and is not subject to
+        /// the admin delay or a veto.
+        ///
+        /// # <weight>
+        /// - O(1) lookup and insert
+        /// # </weight>
+        #[weight = <T as Trait>::WeightInfo::unbond()]
+        pub fn unbond(origin) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let mut bond = Self::bonds(&who).ok_or(Error::<T>::NotBonded)?;
+            ensure!(bond.unlocking_at.is_none(), Error::<T>::AlreadyUnbonding);
+
+            if Self::is_relayer(&who) {
+                Self::unregister_relayer(who.clone())?;
+            }
+            let unlocking_at = <frame_system::Module<T>>::block_number() + T::UnbondingDelay::get();
+            bond.unlocking_at = Some(unlocking_at);
+            <Bonds<T>>::insert(&who, bond);
+
+            Self::deposit_event(RawEvent::Unbonding(who, unlocking_at));
+            Ok(())
+        }
+
+        /// Releases a bond once its unbonding delay has passed.
+        ///
+        /// # <weight>
+        /// - O(1) lookup and unreserve
+        /// # </weight>
+        #[weight = <T as Trait>::WeightInfo::withdraw_unbonded()]
+        pub fn withdraw_unbonded(origin) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let bond = Self::bonds(&who).ok_or(Error::<T>::NotBonded)?;
+            let unlocking_at = bond.unlocking_at.ok_or(Error::<T>::NotUnbonding)?;
+            ensure!(
+                unlocking_at <= <frame_system::Module<T>>::block_number(),
+                Error::<T>::BondStillLocked
+            );
+
+            T::Currency::unreserve(&who, bond.amount);
+            <Bonds<T>>::remove(&who);
+
+            Self::deposit_event(RawEvent::Withdrawn(who, bond.amount));
+            Ok(())
         }
 
         /// Commits a vote in favour of the provided proposal.
         ///
         /// If a proposal with the given nonce and source chain ID does not already exist, it will
-        /// be created with an initial vote in favour from the caller.
+        /// be created with an initial vote in favour from the caller. Creating a proposal requires
+        /// the full call, later votes may reference it by hash. Only relayers that were active
+        /// when the proposal was created can vote on it.
+        ///
+        /// Accepted votes are free. The weight of the proposed call is refunded unless this vote
+        /// executes it, in which case only the weight it actually used is charged.
         ///
         /// # <weight>
-        /// - weight of proposed call, regardless of whether execution is performed
+        /// - O(R) in the number of relayers and O(P) in the size of the encoded proposal
+        /// - weight of proposed call, refunded if execution is not performed
         /// # </weight>
-        #[weight = (call.get_dispatch_info().weight + 195_000_000, call.get_dispatch_info().class, Pays::Yes)]
-        pub fn acknowledge_proposal(origin, nonce: DepositNonce, src_id: ChainId, r_id: ResourceId, call: Box<<T as Trait>::Proposal>) -> DispatchResult {
+        #[weight = {
+            let info = Module::<T>::proposal_dispatch_info(*src_id, *nonce, call);
+            let weight = <T as Trait>::WeightInfo::acknowledge_proposal(
+                <RelayerCount>::get(),
+                call.encode().len() as u32,
+            );
+            (info.weight.saturating_add(weight), info.class, Pays::Yes)
+        }]
+        pub fn acknowledge_proposal(origin, nonce: DepositNonce, src_id: T::ChainId, r_id: ResourceId, call: ProposalOrHashOf<T>) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
             ensure!(Self::is_relayer(&who), Error::<T>::MustBeRelayer);
-            ensure!(Self::chain_whitelisted(src_id), Error::<T>::ChainNotWhitelisted);
-            ensure!(Self::resource_exists(r_id), Error::<T>::ResourceDoesNotExist);
-            ensure!(<dorr::Module<T>>::sorted_active_relayers().binary_search(&who).is_ok(), Error::<T>::MustBeActiveRelayer);
-
-            Self::vote_for(who, nonce, src_id, call)
+            let weight = Self::vote_weight(&call, true);
+            let call_weight = Self::vote(who, nonce, src_id, r_id, call, true, &mut None)?;
+            Ok(PostDispatchInfo {
+                actual_weight: Some(weight.saturating_add(call_weight)),
+                pays_fee: Pays::No,
+            })
         }
 
         /// Commits a vote against a provided proposal.
         ///
+        /// Accepted votes are free.
+        ///
         /// # <weight>
-        /// - Fixed, since execution of proposal should not be included
+        /// - O(R) in the number of relayers, since execution of proposal should not be included
         /// # </weight>
-        #[weight = 195_000_000]
-        pub fn reject_proposal(origin, nonce: DepositNonce, src_id: ChainId, r_id: ResourceId, call: Box<<T as Trait>::Proposal>) -> DispatchResult {
+        #[weight = <T as Trait>::WeightInfo::reject_proposal(<RelayerCount>::get(), call.encode().len() as u32)]
+        pub fn reject_proposal(origin, nonce: DepositNonce, src_id: T::ChainId, r_id: ResourceId, call: ProposalOrHashOf<T>) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
             ensure!(Self::is_relayer(&who), Error::<T>::MustBeRelayer);
-            ensure!(Self::chain_whitelisted(src_id), Error::<T>::ChainNotWhitelisted);
-            ensure!(Self::resource_exists(r_id), Error::<T>::ResourceDoesNotExist);
-            ensure!(<dorr::Module<T>>::sorted_active_relayers().binary_search(&who).is_ok(), Error::<T>::MustBeActiveRelayer);
+            let weight = Self::vote_weight(&call, false);
+            let call_weight = Self::vote(who, nonce, src_id, r_id, call, false, &mut None)?;
+            Ok(PostDispatchInfo {
+                actual_weight: Some(weight.saturating_add(call_weight)),
+                pays_fee: Pays::No,
+            })
+        }
 
-            Self::vote_against(who, nonce, src_id, call)
+        /// Commits votes in favour of several proposals, as `acknowledge_proposal` does for each.
+        ///
+        /// A failing vote doesn't abort the batch. Its changes are reverted and the outcome of
+        /// every vote is reported in a `BatchVoteProcessed` event. The batch is free if every vote
+        /// is accepted, and the weight of proposed calls that are not executed is refunded.
+        ///
+        /// # <weight>
+        /// - O(V) in the number of votes, O(R) in the number of relayers and O(P) in the total
+        ///   size of the encoded proposals
+        /// - weight of the proposed calls that are included in full, refunded if not executed
+        /// # </weight>
+        #[weight = (
+            Module::<T>::batch_vote_weight(votes, true),
+            DispatchClass::Normal,
+            Pays::Yes,
+        )]
+        pub fn acknowledge_proposals_batch(origin, votes: Vec<BatchVoteOf<T>>) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::vote_batch(who, votes, true)
+        }
+
+        /// Commits votes against several proposals, as `reject_proposal` does for each.
+        ///
+        /// A failing vote doesn't abort the batch. Its changes are reverted and the outcome of
+        /// every vote is reported in a `BatchVoteProcessed` event. The batch is free if every vote
+        /// is accepted.
+        ///
+        /// # <weight>
+        /// - O(V) in the number of votes, O(R) in the number of relayers and O(P) in the total
+        ///   size of the encoded proposals
+        /// # </weight>
+        #[weight = (
+            Module::<T>::batch_vote_weight(votes, false),
+            DispatchClass::Normal,
+            Pays::Yes,
+        )]
+        pub fn reject_proposals_batch(origin, votes: Vec<BatchVoteOf<T>>) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::vote_batch(who, votes, false)
         }
 
         /// Evaluate the state of a proposal given the current vote threshold.
         ///
         /// A proposal with enough votes will be either executed or cancelled, and the status
-        /// will be updated accordingly.
+        /// will be updated accordingly. The weight of the proposed call is refunded unless it is
+        /// executed.
         ///
         /// # <weight>
+        /// - O(R) in the number of relayers and O(P) in the size of the encoded proposal
         /// - weight of proposed call, regardless of whether execution is performed
         /// # </weight>
-        #[weight = (prop.get_dispatch_info().weight + 195_000_000, prop.get_dispatch_info().class, Pays::Yes)]
-        pub fn eval_vote_state(origin, nonce: DepositNonce, src_id: ChainId, prop: Box<<T as Trait>::Proposal>) -> DispatchResult {
+        #[weight = {
+            let info = Module::<T>::proposal_dispatch_info(*src_id, *nonce, prop);
+            let weight = <T as Trait>::WeightInfo::eval_vote_state(
+                <RelayerCount>::get(),
+                prop.encode().len() as u32,
+            );
+            (info.weight.saturating_add(weight), info.class, Pays::Yes)
+        }]
+        pub fn eval_vote_state(origin, nonce: DepositNonce, src_id: T::ChainId, prop: ProposalOrHashOf<T>) -> DispatchResultWithPostInfo {
             ensure_signed(origin)?;
-
-            Self::try_resolve_proposal(nonce, src_id, prop)
+            Self::ensure_chain_enabled(src_id)?;
+
+            let weight = <T as Trait>::WeightInfo::eval_vote_state(
+                <RelayerCount>::get(),
+                prop.encode().len() as u32,
+            );
+            let (prop_hash, prop) = Self::resolve_proposal(src_id, nonce, prop)?;
+            let votes = Self::votes((src_id, nonce), prop_hash).ok_or(Error::<T>::ProposalDoesNotExist)?;
+            let resource = Self::inbound_resource(src_id, votes.resource_id)?;
+            ensure!(
+                prop.using_encoded(|call| resource.matches_call(call)),
+                Error::<T>::CallMismatch
+            );
+            let call_weight = Self::try_resolve_proposal(nonce, src_id, prop_hash, prop)?;
+            Ok(Some(weight.saturating_add(call_weight)).into())
         }
     }
 }
@@ -353,31 +1321,370 @@ impl<T: Trait> Module<T> {
         Self::relayers(who)
     }
 
+    /// Ensures that who has reserved the full relayer bond and isn't unbonding, if bonding is
+    /// enabled. Checked when relayers join the set and when they vote, so that slashed relayers
+    /// must top up their bond to vote again.
+    fn ensure_bonded(who: &T::AccountId) -> DispatchResult {
+        let required = T::RelayerBond::get();
+        if required.is_zero() {
+            return Ok(());
+        }
+        let bond = Self::bonds(who).unwrap_or_default();
+        ensure!(
+            bond.unlocking_at.is_none() && bond.amount >= required,
+            Error::<T>::InsufficientBond
+        );
+        Ok(())
+    }
+
     /// Provides an AccountId for the pallet.
     /// This is used both as an origin check and deposit/withdrawal account.
     pub fn account_id() -> T::AccountId {
         MODULE_ID.into_account()
     }
 
+    /// Provides the AccountId of the pot holding transfer fees until they are paid to relayers.
+    pub fn reward_pot_id() -> T::AccountId {
+        REWARD_POT_ID.into_account()
+    }
+
     /// Asserts if a resource is registered
     pub fn resource_exists(id: ResourceId) -> bool {
         return Self::resources(id) != None;
     }
 
-    /// Checks if a chain exists as a whitelisted destination
-    pub fn chain_whitelisted(id: ChainId) -> bool {
-        return Self::chains(id) != None;
+    /// Checks if a chain exists as a whitelisted destination and isn't disabled
+    pub fn chain_whitelisted(id: T::ChainId) -> bool {
+        return Self::chains(id) != None && !Self::chain_disabled(id);
+    }
+
+    /// Ensures a chain is whitelisted and isn't disabled
+    fn ensure_chain_enabled(id: T::ChainId) -> DispatchResult {
+        ensure!(Self::chains(id) != None, Error::<T>::ChainNotWhitelisted);
+        ensure!(!Self::chain_disabled(id), Error::<T>::ChainDisabled);
+        Ok(())
     }
 
     /// Increments the deposit nonce for the specified chain ID
-    fn bump_nonce(id: ChainId) -> DepositNonce {
+    fn bump_nonce(id: T::ChainId) -> DepositNonce {
         let nonce = Self::chains(id).unwrap_or_default() + 1;
-        <ChainNonces>::insert(id, nonce);
+        <ChainNonces<T>>::insert(id, nonce);
         nonce
     }
 
+    /// Records an outbound deposit and drops the record that falls out of the retention window.
+    fn record_deposit(
+        dest_id: T::ChainId,
+        nonce: DepositNonce,
+        resource_id: ResourceId,
+        payload: DepositPayload,
+    ) {
+        let retention = T::DepositRetention::get();
+        if retention == 0 {
+            return;
+        }
+        let record = DepositRecord {
+            resource_id,
+            payload,
+            block: <frame_system::Module<T>>::block_number(),
+        };
+        <DepositRecords<T>>::insert(dest_id, nonce, record);
+        if nonce > retention {
+            <DepositRecords<T>>::remove(dest_id, nonce - retention);
+        }
+    }
+
+    /// Returns the records of up to `count` consecutive deposits to `dest_id`, starting at
+    /// nonce `from`. At most `DepositRetention` records are returned, and deposits whose record
+    /// was pruned are skipped.
+    pub fn deposit_records(
+        dest_id: T::ChainId,
+        from: DepositNonce,
+        count: u32,
+    ) -> Vec<(DepositNonce, DepositRecord<T::BlockNumber>)> {
+        let count = sp_std::cmp::min(count as DepositNonce, T::DepositRetention::get());
+        (from..from.saturating_add(count))
+            .filter_map(|nonce| Self::deposit_record(dest_id, nonce).map(|record| (nonce, record)))
+            .collect()
+    }
+
+    /// Returns all currently paused scopes
+    pub fn paused_scopes() -> Vec<PauseScopeOf<T>> {
+        <Paused<T>>::iter().map(|(scope, _)| scope).collect()
+    }
+
+    /// Returns all whitelisted chains, including disabled ones, with their outbound deposit nonces
+    pub fn whitelisted_chains() -> Vec<(T::ChainId, DepositNonce)> {
+        <ChainNonces<T>>::iter().collect()
+    }
+
+    /// Returns all accounts in the relayer set
+    pub fn relayer_set() -> Vec<T::AccountId> {
+        <Relayers<T>>::iter()
+            .filter(|(_, is_relayer)| *is_relayer)
+            .map(|(relayer, _)| relayer)
+            .collect()
+    }
+
+    /// Returns all registered resources
+    pub fn registered_resources() -> Vec<(ResourceId, ResourceOf<T>)> {
+        <Resources<T>>::iter().collect()
+    }
+
+    /// Returns up to `limit` proposals known for a deposit, keyed by the hash of their call.
+    /// Proposals are returned in storage order, starting after the proposal with the hash `start`
+    /// if given, so that the proposals of a deposit can be queried in pages.
+    pub fn proposals(
+        src_id: T::ChainId,
+        nonce: DepositNonce,
+        start: Option<T::Hash>,
+        limit: u32,
+    ) -> Vec<(T::Hash, ProposalVotes<T::AccountId, T::BlockNumber>)> {
+        let prefix = <Votes<T>>::storage_double_map_final_key1((src_id, nonce));
+        let mut previous_key = match start {
+            Some(prop_hash) => <Votes<T>>::hashed_key_for((src_id, nonce), prop_hash),
+            None => prefix.clone(),
+        };
+        let mut proposals = Vec::new();
+        while proposals.len() < limit as usize {
+            let key = match sp_io::storage::next_key(&previous_key).filter(|k| k.starts_with(&prefix)) {
+                Some(key) => key,
+                None => break,
+            };
+            // The hash is keyed with `blake2_128_concat`, so it follows its 16 byte hash
+            let key2 = key.get(prefix.len() + 16..).unwrap_or_default();
+            if let Ok(prop_hash) = T::Hash::decode(&mut &key2[..]) {
+                if let Some(votes) = Self::votes((src_id, nonce), prop_hash) {
+                    proposals.push((prop_hash, votes));
+                }
+            }
+            previous_key = key;
+        }
+        proposals
+    }
+
+    /// Ensures a transfer in the given direction is not halted by any pause.
+    /// The resource is only checked if it's known.
+    fn ensure_not_paused(
+        direction: BridgeDirection,
+        chain_id: T::ChainId,
+        resource_id: Option<ResourceId>,
+    ) -> DispatchResult {
+        ensure!(!Self::paused(PauseScope::Bridge), Error::<T>::BridgePaused);
+        ensure!(
+            !Self::paused(PauseScope::Direction(direction)),
+            Error::<T>::DirectionPaused
+        );
+        ensure!(
+            !Self::paused(PauseScope::Chain(chain_id)),
+            Error::<T>::ChainPaused
+        );
+        if let Some(id) = resource_id {
+            ensure!(
+                !Self::paused(PauseScope::Resource(id)),
+                Error::<T>::ResourcePaused
+            );
+        }
+        Ok(())
+    }
+
+    /// Sets or removes the rate limit of a resource to or from a chain. The usage counted
+    /// against the previous limit is cleared.
+    fn set_resource_rate_limit(
+        direction: BridgeDirection,
+        chain_id: T::ChainId,
+        resource_id: ResourceId,
+        limit: Option<RateLimit<T::BlockNumber>>,
+    ) -> DispatchResult {
+        let key = (direction, chain_id, resource_id);
+        match limit.clone() {
+            Some(limit) => {
+                ensure!(!limit.window.is_zero(), Error::<T>::InvalidRateLimit);
+                <RateLimits<T>>::insert(&key, limit);
+            }
+            None => <RateLimits<T>>::remove(&key),
+        }
+        <RateLimitUsage<T>>::remove(&key);
+        Self::deposit_event(RawEvent::RateLimitChanged(
+            direction,
+            chain_id,
+            resource_id,
+            limit,
+        ));
+        Ok(())
+    }
+
+    /// Ensures a transfer of `amount` fits into the rate limit of the resource and chain.
+    /// Returns the usage of the limit including the transfer, which must be stored with
+    /// `note_rate_limit_usage` once the transfer is made, or `None` if there is no limit.
+    ///
+    /// The window of a limit is tracked in `RATE_LIMIT_BUCKETS` buckets, and a bucket is
+    /// counted until the window has fully passed since its start.
+    fn check_rate_limit(
+        direction: BridgeDirection,
+        chain_id: T::ChainId,
+        resource_id: ResourceId,
+        amount: U256,
+    ) -> Result<Option<Vec<RateLimitBucket<T::BlockNumber>>>, DispatchError> {
+        let key = (direction, chain_id, resource_id);
+        let limit = match Self::rate_limit(&key) {
+            Some(limit) => limit,
+            None => return Ok(None),
+        };
+        let now = <frame_system::Module<T>>::block_number();
+        let bucket_len = (limit.window / T::BlockNumber::from(RATE_LIMIT_BUCKETS)).max(One::one());
+        let bucket = now - now % bucket_len;
+
+        let mut usage = Self::rate_limit_usage(&key);
+        usage.retain(|(start, _, _)| start.saturating_add(limit.window) > now);
+
+        let (used_amount, used_count) = usage.iter().fold(
+            (U256::zero(), 0u32),
+            |(total, count), (_, a, c)| (total.saturating_add(*a), count.saturating_add(*c)),
+        );
+        if let Some(max) = limit.max_amount {
+            ensure!(
+                used_amount.saturating_add(amount) <= max,
+                Error::<T>::RateLimitExceeded
+            );
+        }
+        if let Some(max) = limit.max_count {
+            ensure!(used_count < max, Error::<T>::RateLimitExceeded);
+        }
+
+        match usage.last_mut() {
+            Some((start, a, c)) if *start == bucket => {
+                *a = a.saturating_add(amount);
+                *c = c.saturating_add(1);
+            }
+            _ => usage.push((bucket, amount, 1)),
+        }
+        Ok(Some(usage))
+    }
+
+    /// Stores the usage of a rate limit returned by `check_rate_limit`.
+    fn note_rate_limit_usage(
+        direction: BridgeDirection,
+        chain_id: T::ChainId,
+        resource_id: ResourceId,
+        usage: Option<Vec<RateLimitBucket<T::BlockNumber>>>,
+    ) {
+        if let Some(usage) = usage {
+            <RateLimitUsage<T>>::insert((direction, chain_id, resource_id), usage);
+        }
+    }
+
+    /// Checks if a proposal for the inbound deposit nonce has already been executed
+    pub fn nonce_executed(src_id: T::ChainId, nonce: DepositNonce) -> bool {
+        nonce <= Self::executed_nonce_watermark(src_id) || Self::executed_nonces(src_id, nonce)
+    }
+
+    /// Marks an inbound deposit nonce as executed, advancing the watermark over any
+    /// consecutive nonces that were executed out of order.
+    fn mark_nonce_executed(src_id: T::ChainId, nonce: DepositNonce) {
+        let mut watermark = Self::executed_nonce_watermark(src_id);
+        if nonce <= watermark {
+            return;
+        }
+        if nonce != watermark + 1 {
+            <ExecutedNonces<T>>::insert(src_id, nonce, true);
+            return;
+        }
+
+        watermark = nonce;
+        while <ExecutedNonces<T>>::take(src_id, watermark + 1) {
+            watermark += 1;
+        }
+        <ExecutedNonceWatermark<T>>::insert(src_id, watermark);
+    }
+
     // *** Admin methods ***
 
+    /// Applies an admin change, or schedules it if an admin delay is configured.
+    /// The change is validated against the current state when scheduled and again when applied.
+    pub fn schedule_admin_action(action: AdminActionOf<T>) -> DispatchResult {
+        let delay = T::AdminDelay::get();
+        if delay.is_zero() {
+            return Self::apply_admin_action(action);
+        }
+        Self::validate_admin_action(&action)?;
+
+        let id = Self::next_admin_action_id();
+        let at = <frame_system::Module<T>>::block_number() + delay;
+        <NextAdminActionId>::put(id.wrapping_add(1));
+        <ScheduledAdminActions<T>>::insert(id, (at, action.clone()));
+        <AdminActionQueue<T>>::append(at, id);
+        Self::deposit_event(RawEvent::AdminActionScheduled(id, action, at));
+        Ok(())
+    }
+
+    /// Checks that an admin change would succeed in the current state
+    fn validate_admin_action(action: &AdminActionOf<T>) -> DispatchResult {
+        match action {
+            AdminAction::SetThreshold(threshold) => {
+                ensure!(*threshold > 0, Error::<T>::InvalidThreshold);
+            }
+            AdminAction::AddRelayer(relayer) => {
+                ensure!(!Self::is_relayer(relayer), Error::<T>::RelayerAlreadyExists);
+                Self::ensure_bonded(relayer)?;
+            }
+            AdminAction::RemoveRelayer(relayer) => {
+                ensure!(Self::is_relayer(relayer), Error::<T>::RelayerInvalid);
+            }
+            AdminAction::SetThresholdFraction(fraction) => {
+                ensure!(*fraction != Some(Perbill::from_parts(0)), Error::<T>::InvalidThreshold);
+            }
+            AdminAction::SetChainThreshold(id, threshold) => {
+                ensure!(Self::chains(*id) != None, Error::<T>::ChainNotWhitelisted);
+                ensure!(*threshold != Some(0), Error::<T>::InvalidThreshold);
+            }
+        }
+        Ok(())
+    }
+
+    fn apply_admin_action(action: AdminActionOf<T>) -> DispatchResult {
+        match action {
+            AdminAction::SetThreshold(threshold) => Self::set_relayer_threshold(threshold),
+            AdminAction::AddRelayer(relayer) => Self::register_relayer(relayer),
+            AdminAction::RemoveRelayer(relayer) => Self::unregister_relayer(relayer),
+            AdminAction::SetThresholdFraction(fraction) => {
+                Self::set_relayer_threshold_fraction(fraction)
+            }
+            AdminAction::SetChainThreshold(id, threshold) => {
+                Self::set_chain_relayer_threshold(id, threshold)
+            }
+        }
+    }
+
+    /// Applies the admin changes scheduled for block `now` that have not been vetoed.
+    fn apply_admin_actions(now: T::BlockNumber) -> Weight {
+        let ids = <AdminActionQueue<T>>::take(now);
+        let mut weight = T::DbWeight::get().reads_writes(1, 1);
+        for id in ids {
+            weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+            if let Some((_, action)) = <ScheduledAdminActions<T>>::take(id) {
+                weight = weight.saturating_add(Self::admin_action_weight(&action));
+                let result = Self::apply_admin_action(action);
+                Self::deposit_event(RawEvent::AdminActionApplied(id, result));
+            }
+        }
+        weight
+    }
+
+    /// Weight of applying an admin change, the weight of the call that makes the change
+    fn admin_action_weight(action: &AdminActionOf<T>) -> Weight {
+        match action {
+            AdminAction::SetThreshold(_) => <T as Trait>::WeightInfo::set_threshold(),
+            AdminAction::AddRelayer(_) => <T as Trait>::WeightInfo::add_relayer(),
+            // Also removes the DORR entries of the relayer
+            AdminAction::RemoveRelayer(_) => <T as Trait>::WeightInfo::remove_relayer(),
+            AdminAction::SetThresholdFraction(_) => {
+                <T as Trait>::WeightInfo::set_threshold_fraction()
+            }
+            AdminAction::SetChainThreshold(..) => <T as Trait>::WeightInfo::set_chain_threshold(),
+        }
+    }
+
     /// Set a new voting threshold
     pub fn set_relayer_threshold(threshold: u32) -> DispatchResult {
         ensure!(threshold > 0, Error::<T>::InvalidThreshold);
@@ -386,38 +1693,204 @@ impl<T: Trait> Module<T> {
         Ok(())
     }
 
-    /// Register a method for a resource Id, enabling associated transfers
-    pub fn register_resource(id: ResourceId, method: Vec<u8>) -> DispatchResult {
-        <Resources>::insert(id, method);
+    /// Set or clear the voting threshold as a fraction of the active relayers
+    pub fn set_relayer_threshold_fraction(fraction: Option<Perbill>) -> DispatchResult {
+        ensure!(fraction != Some(Perbill::from_parts(0)), Error::<T>::InvalidThreshold);
+        <RelayerThresholdFraction>::set(fraction);
+        Self::deposit_event(RawEvent::RelayerThresholdFractionChanged(fraction));
+        Ok(())
+    }
+
+    /// Set or clear the voting threshold override of a whitelisted chain
+    pub fn set_chain_relayer_threshold(id: T::ChainId, threshold: Option<u32>) -> DispatchResult {
+        ensure!(Self::chains(id) != None, Error::<T>::ChainNotWhitelisted);
+        ensure!(threshold != Some(0), Error::<T>::InvalidThreshold);
+        match threshold {
+            Some(threshold) => <ChainThresholds<T>>::insert(id, threshold),
+            None => <ChainThresholds<T>>::remove(id),
+        }
+        Self::deposit_event(RawEvent::ChainThresholdChanged(id, threshold));
         Ok(())
     }
 
+    /// Returns the number of votes required to approve a proposal from `src_id` with `voters`
+    /// eligible relayers, and the number of relayers that can vote on it.
+    pub fn vote_threshold(src_id: T::ChainId, voters: u32) -> (u32, u32) {
+        if let Some(threshold) = Self::chain_threshold(src_id) {
+            return (threshold, voters);
+        }
+        match Self::relayer_threshold_fraction() {
+            Some(fraction) => (fraction.mul_ceil(voters).max(1), voters),
+            None => (<RelayerThreshold>::get(), voters),
+        }
+    }
+
+    /// Returns the number of votes a new proposal from `src_id` requires, which takes the chain
+    /// override and the threshold fraction into account.
+    pub fn effective_threshold(src_id: T::ChainId) -> u32 {
+        Self::vote_threshold(src_id, Self::eligible_voters().len() as u32).0
+    }
+
+    /// Registered relayers that are active in DORR, who are eligible to vote on new proposals.
+    /// Sorted by account, as the active set is elected once per epoch and cached by DORR.
+    pub fn eligible_voters() -> Vec<T::AccountId> {
+        <dorr::Module<T>>::sorted_active_relayers()
+            .into_iter()
+            .filter(|r| Self::is_relayer(r))
+            .collect()
+    }
+
+    /// Registers a resource under a resource Id, enabling associated transfers
+    pub fn register_resource(id: ResourceId, resource: ResourceOf<T>) -> DispatchResult {
+        <Resources<T>>::insert(id, resource);
+        Ok(())
+    }
+
+    /// Returns a registered resource, ensuring it's enabled
+    fn enabled_resource(id: ResourceId) -> Result<ResourceOf<T>, DispatchError> {
+        let resource = Self::resources(id).ok_or(Error::<T>::ResourceDoesNotExist)?;
+        ensure!(resource.enabled, Error::<T>::ResourceDisabled);
+        Ok(resource)
+    }
+
+    /// Returns the registered resource of an inbound proposal from `src_id`, ensuring it's
+    /// enabled and not paused
+    fn inbound_resource(
+        src_id: T::ChainId,
+        resource_id: ResourceId,
+    ) -> Result<ResourceOf<T>, DispatchError> {
+        let resource = Self::enabled_resource(resource_id)?;
+        Self::ensure_not_paused(BridgeDirection::Inbound, src_id, Some(resource_id))?;
+        Ok(resource)
+    }
+
+    /// Returns the registered resource of an outbound transfer, ensuring it's enabled and
+    /// can be transferred to `dest_id`
+    fn outbound_resource(
+        dest_id: T::ChainId,
+        resource_id: ResourceId,
+    ) -> Result<ResourceOf<T>, DispatchError> {
+        let resource = Self::enabled_resource(resource_id)?;
+        ensure!(
+            resource.allows_destination(dest_id),
+            Error::<T>::DestinationNotAllowed
+        );
+        Ok(resource)
+    }
+
     /// Removes a resource ID, disabling associated transfer
     pub fn unregister_resource(id: ResourceId) -> DispatchResult {
-        <Resources>::remove(id);
+        <Resources<T>>::remove(id);
+        Ok(())
+    }
+
+    /// Pauses or resumes transfers within a scope
+    pub fn set_paused(scope: PauseScopeOf<T>, paused: bool) -> DispatchResult {
+        if paused {
+            ensure!(!Self::paused(&scope), Error::<T>::AlreadyPaused);
+            <Paused<T>>::insert(&scope, true);
+            Self::deposit_event(RawEvent::Paused(scope));
+        } else {
+            ensure!(Self::paused(&scope), Error::<T>::NotPaused);
+            <Paused<T>>::remove(&scope);
+            Self::deposit_event(RawEvent::Unpaused(scope));
+        }
+        Ok(())
+    }
+
+    /// Set the fee for outbound transfers to a chain
+    pub fn set_bridge_fee(dest_id: T::ChainId, fee: BalanceOf<T>) -> DispatchResult {
+        if fee.is_zero() {
+            <BridgeFees<T>>::remove(dest_id);
+        } else {
+            <BridgeFees<T>>::insert(dest_id, fee);
+        }
+        Self::deposit_event(RawEvent::FeeChanged(dest_id, fee));
         Ok(())
     }
 
     /// Whitelist a chain ID for transfer
-    pub fn whitelist(id: ChainId) -> DispatchResult {
-        // Cannot whitelist this chain
-        ensure!(id != T::ChainId::get(), Error::<T>::InvalidChainId);
-        // Cannot whitelist with an existing entry
+    pub fn whitelist(id: T::ChainId) -> DispatchResult {
+        // Cannot whitelist this chain, or a chain outside of the chain ID format
+        ensure!(id != T::ChainIdentity::get(), Error::<T>::InvalidChainId);
         ensure!(
-            !Self::chain_whitelisted(id),
+            Into::<u32>::into(id) <= T::ChainIdFormat::get().max_chain_id(),
+            Error::<T>::InvalidChainId
+        );
+        // Cannot whitelist with an existing entry, even if it's disabled
+        ensure!(
+            Self::chains(id) == None,
             Error::<T>::ChainAlreadyWhitelisted
         );
-        <ChainNonces>::insert(&id, 0);
+        <ChainNonces<T>>::insert(&id, 0);
         Self::deposit_event(RawEvent::ChainWhitelisted(id));
         Ok(())
     }
 
+    /// Sets or removes the configuration of a whitelisted chain
+    pub fn configure_chain(
+        id: T::ChainId,
+        config: Option<ChainConfig<T::BlockNumber>>,
+    ) -> DispatchResult {
+        ensure!(Self::chains(id) != None, Error::<T>::ChainNotWhitelisted);
+        if let Some(config) = &config {
+            let lifetime_valid = config.proposal_lifetime.map_or(true, |l| !l.is_zero());
+            let format_valid = match config.address_format {
+                AddressFormat::LengthRange(min, max) => min <= max,
+                _ => true,
+            };
+            ensure!(
+                lifetime_valid && format_valid,
+                Error::<T>::InvalidChainConfig
+            );
+            <ChainConfigs<T>>::insert(id, config);
+        } else {
+            <ChainConfigs<T>>::remove(id);
+        }
+        Self::deposit_event(RawEvent::ChainConfigChanged(id, config));
+        Ok(())
+    }
+
+    /// Ensures a recipient address has the format of the destination chain, if it's configured
+    fn ensure_valid_recipient(dest_id: T::ChainId, recipient: &[u8]) -> DispatchResult {
+        if let Some(config) = Self::chain_config(dest_id) {
+            ensure!(
+                config.address_format.is_valid(recipient),
+                Error::<T>::InvalidRecipient
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns the lifetime of proposals from a chain
+    fn proposal_lifetime(src_id: T::ChainId) -> T::BlockNumber {
+        Self::chain_config(src_id)
+            .and_then(|config| config.proposal_lifetime)
+            .unwrap_or_else(T::ProposalLifetime::get)
+    }
+
+    /// Disables or enables a whitelisted chain
+    pub fn set_chain_disabled(id: T::ChainId, disabled: bool) -> DispatchResult {
+        ensure!(Self::chains(id) != None, Error::<T>::ChainNotWhitelisted);
+        if disabled {
+            ensure!(!Self::chain_disabled(id), Error::<T>::ChainAlreadyDisabled);
+            <DisabledChains<T>>::insert(id, true);
+            Self::deposit_event(RawEvent::ChainDisabled(id));
+        } else {
+            ensure!(Self::chain_disabled(id), Error::<T>::ChainNotDisabled);
+            <DisabledChains<T>>::remove(id);
+            Self::deposit_event(RawEvent::ChainEnabled(id));
+        }
+        Ok(())
+    }
+
     /// Adds a new relayer to the set
     pub fn register_relayer(relayer: T::AccountId) -> DispatchResult {
         ensure!(
             !Self::is_relayer(&relayer),
             Error::<T>::RelayerAlreadyExists
         );
+        Self::ensure_bonded(&relayer)?;
         <Relayers<T>>::insert(&relayer, true);
         <RelayerCount>::mutate(|i| *i += 1);
 
@@ -425,38 +1898,180 @@ impl<T: Trait> Module<T> {
         Ok(())
     }
 
-    /// Removes a relayer from the set
+    /// Removes a relayer from the set, along with its DORR key and VRF result
     pub fn unregister_relayer(relayer: T::AccountId) -> DispatchResult {
         ensure!(Self::is_relayer(&relayer), Error::<T>::RelayerInvalid);
         <Relayers<T>>::remove(&relayer);
         <RelayerCount>::mutate(|i| *i -= 1);
+        <dorr::Module<T>>::remove_candidate(&relayer);
         Self::deposit_event(RawEvent::RelayerRemoved(relayer));
         Ok(())
     }
 
     // *** Proposal voting and execution methods ***
 
+    /// Returns the dispatch info of a proposed call. A call referenced by hash is looked up in
+    /// the preimage store, an unknown hash yields the default info.
+    pub fn proposal_dispatch_info(
+        src_id: T::ChainId,
+        nonce: DepositNonce,
+        prop: &ProposalOrHashOf<T>,
+    ) -> DispatchInfo {
+        match prop {
+            ProposalOrHash::Proposal(call) => call.get_dispatch_info(),
+            ProposalOrHash::Hash(hash) => Self::proposal_preimage((src_id, nonce), *hash)
+                .map(|call| call.get_dispatch_info())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Weight of a vote, excluding the execution of the proposed call
+    fn vote_weight(call: &ProposalOrHashOf<T>, in_favour: bool) -> Weight {
+        let relayers = <RelayerCount>::get();
+        if in_favour {
+            <T as Trait>::WeightInfo::acknowledge_proposal(relayers, call.encode().len() as u32)
+        } else {
+            <T as Trait>::WeightInfo::reject_proposal(relayers, call.encode().len() as u32)
+        }
+    }
+
+    /// Weight of a batch of votes. Votes in favour are charged the weight of the proposed call,
+    /// looked up in the preimage store for votes by hash as `acknowledge_proposal` does. An
+    /// oversized batch is charged as many votes as a batch may hold, and fails once dispatched.
+    pub fn batch_vote_weight(votes: &[BatchVoteOf<T>], in_favour: bool) -> Weight {
+        let votes = &votes[..sp_std::cmp::min(votes.len(), T::MaxVotesPerBatch::get() as usize)];
+        let base = Self::batch_base_weight(votes, in_favour);
+        if !in_favour {
+            return base;
+        }
+        votes.iter().fold(base, |total, (nonce, src_id, _, call)| {
+            total.saturating_add(Self::proposal_dispatch_info(*src_id, *nonce, call).weight)
+        })
+    }
+
+    /// Weight of a batch of votes, excluding the execution of proposed calls
+    fn batch_base_weight(votes: &[BatchVoteOf<T>], in_favour: bool) -> Weight {
+        let relayers = <RelayerCount>::get();
+        let size = votes.iter().fold(0u32, |size, (_, _, _, call)| {
+            size.saturating_add(call.encode().len() as u32)
+        });
+        if in_favour {
+            <T as Trait>::WeightInfo::acknowledge_proposals_batch(votes.len() as u32, relayers, size)
+        } else {
+            <T as Trait>::WeightInfo::reject_proposals_batch(votes.len() as u32, relayers, size)
+        }
+    }
+
+    /// Commits a vote of a relayer on a proposal, and resolves the proposal if possible.
+    /// Returns the weight used by the proposed call if it was executed. The vote is applied in
+    /// its own storage transaction, so a failing vote is reverted.
+    ///
+    /// `eligible` caches the active relayers used as voters of new proposals, so that a batch of
+    /// votes only computes them once.
+    fn vote(
+        who: T::AccountId,
+        nonce: DepositNonce,
+        src_id: T::ChainId,
+        r_id: ResourceId,
+        call: ProposalOrHashOf<T>,
+        in_favour: bool,
+        eligible: &mut Option<Vec<T::AccountId>>,
+    ) -> Result<Weight, DispatchError> {
+        Self::ensure_bonded(&who)?;
+        Self::ensure_chain_enabled(src_id)?;
+        let resource = Self::inbound_resource(src_id, r_id)?;
+
+        let (prop_hash, prop) = Self::resolve_proposal(src_id, nonce, call)?;
+        ensure!(
+            prop.using_encoded(|call| resource.matches_call(call)),
+            Error::<T>::CallMismatch
+        );
+        with_transaction(|| {
+            let res = if in_favour {
+                Self::vote_for(who, nonce, src_id, r_id, prop_hash, prop, eligible)
+            } else {
+                Self::vote_against(who, nonce, src_id, r_id, prop_hash, prop, eligible)
+            };
+            if res.is_ok() {
+                TransactionOutcome::Commit(res)
+            } else {
+                TransactionOutcome::Rollback(res)
+            }
+        })
+    }
+
+    /// Commits a batch of votes. A failing vote is reverted without affecting the others.
+    /// The batch only pays a fee if one of the votes failed.
+    fn vote_batch(
+        who: T::AccountId,
+        votes: Vec<BatchVoteOf<T>>,
+        in_favour: bool,
+    ) -> DispatchResultWithPostInfo {
+        ensure!(
+            votes.len() as u32 <= T::MaxVotesPerBatch::get(),
+            Error::<T>::TooManyVotes
+        );
+        ensure!(Self::is_relayer(&who), Error::<T>::MustBeRelayer);
+
+        let mut eligible = None;
+        let mut actual_weight = Self::batch_base_weight(&votes, in_favour);
+        let mut pays_fee = Pays::No;
+        for (nonce, src_id, r_id, call) in votes {
+            let result = Self::vote(who.clone(), nonce, src_id, r_id, call, in_favour, &mut eligible);
+            match result {
+                Ok(call_weight) => actual_weight = actual_weight.saturating_add(call_weight),
+                Err(_) => pays_fee = Pays::Yes,
+            }
+            Self::deposit_event(RawEvent::BatchVoteProcessed(src_id, nonce, result.map(|_| ())));
+        }
+        Ok(PostDispatchInfo { actual_weight: Some(actual_weight), pays_fee })
+    }
+
+    /// Resolves a proposed call into its hash and full call.
+    fn resolve_proposal(
+        src_id: T::ChainId,
+        nonce: DepositNonce,
+        prop: ProposalOrHashOf<T>,
+    ) -> Result<(T::Hash, Box<T::Proposal>), DispatchError> {
+        match prop {
+            ProposalOrHash::Proposal(call) => Ok((T::Hashing::hash_of(&*call), call)),
+            ProposalOrHash::Hash(hash) => {
+                let call = Self::proposal_preimage((src_id, nonce), hash)
+                    .ok_or(Error::<T>::ProposalPreimageMissing)?;
+                Ok((hash, Box::new(call)))
+            }
+        }
+    }
+
     /// Commits a vote for a proposal. If the proposal doesn't exist it will be created.
     fn commit_vote(
         who: T::AccountId,
         nonce: DepositNonce,
-        src_id: ChainId,
+        src_id: T::ChainId,
+        r_id: ResourceId,
+        prop_hash: T::Hash,
         prop: Box<T::Proposal>,
         in_favour: bool,
+        eligible: &mut Option<Vec<T::AccountId>>,
     ) -> DispatchResult {
         let now = <frame_system::Module<T>>::block_number();
-        let mut votes = match <Votes<T>>::get(src_id, (nonce, prop.clone())) {
-            Some(v) => v,
+        let (mut votes, is_new) = match <Votes<T>>::get((src_id, nonce), prop_hash) {
+            Some(v) => (v, false),
             None => {
                 let mut v = ProposalVotes::default();
-                v.expiry = now + T::ProposalLifetime::get();
-                v
+                v.expiry = now + Self::proposal_lifetime(src_id);
+                v.voters = eligible.get_or_insert_with(Self::eligible_voters).clone();
+                v.resource_id = r_id;
+                (v, true)
             }
         };
 
         // Ensure the proposal isn't complete and relayer hasn't already voted
         ensure!(!votes.is_complete(), Error::<T>::ProposalAlreadyComplete);
+        ensure!(!Self::nonce_executed(src_id, nonce), Error::<T>::NonceAlreadyExecuted);
         ensure!(!votes.is_expired(now), Error::<T>::ProposalExpired);
+        ensure!(votes.resource_id == r_id, Error::<T>::ResourceMismatch);
+        ensure!(votes.is_eligible(&who), Error::<T>::MustBeActiveRelayer);
         ensure!(!votes.has_voted(&who), Error::<T>::RelayerAlreadyVoted);
 
         if in_favour {
@@ -467,29 +2082,63 @@ impl<T: Trait> Module<T> {
             Self::deposit_event(RawEvent::VoteAgainst(src_id, nonce, who.clone()));
         }
 
-        <Votes<T>>::insert(src_id, (nonce, prop.clone()), votes.clone());
+        <Votes<T>>::insert((src_id, nonce), prop_hash, votes.clone());
+
+        if is_new {
+            if T::StorePreimages::get() {
+                <ProposalPreimages<T>>::insert((src_id, nonce), prop_hash, *prop);
+            }
+            Self::schedule_prune(votes.expiry, src_id, nonce, prop_hash);
+        }
 
         Ok(())
     }
 
     /// Attempts to finalize or cancel the proposal if the vote count allows.
+    /// Returns the weight used by the proposed call if it was executed.
     fn try_resolve_proposal(
         nonce: DepositNonce,
-        src_id: ChainId,
+        src_id: T::ChainId,
+        prop_hash: T::Hash,
         prop: Box<T::Proposal>,
-    ) -> DispatchResult {
-        if let Some(mut votes) = <Votes<T>>::get(src_id, (nonce, prop.clone())) {
+    ) -> Result<Weight, DispatchError> {
+        if let Some(mut votes) = <Votes<T>>::get((src_id, nonce), prop_hash) {
             let now = <frame_system::Module<T>>::block_number();
             ensure!(!votes.is_complete(), Error::<T>::ProposalAlreadyComplete);
+            ensure!(!Self::nonce_executed(src_id, nonce), Error::<T>::NonceAlreadyExecuted);
             ensure!(!votes.is_expired(now), Error::<T>::ProposalExpired);
 
-            let status = votes.try_to_complete(<RelayerThreshold>::get(), <RelayerCount>::get());
-            <Votes<T>>::insert(src_id, (nonce, prop.clone()), votes.clone());
+            let (threshold, total) = Self::vote_threshold(src_id, votes.voters.len() as u32);
+            let status = votes.try_to_complete(threshold, total);
+            if status == ProposalStatus::Approved {
+                let usage = Self::check_rate_limit(
+                    BridgeDirection::Inbound,
+                    src_id,
+                    votes.resource_id,
+                    U256::zero(),
+                )?;
+                Self::note_rate_limit_usage(
+                    BridgeDirection::Inbound,
+                    src_id,
+                    votes.resource_id,
+                    usage,
+                );
+            }
+            <Votes<T>>::insert((src_id, nonce), prop_hash, votes.clone());
+
+            if votes.is_complete() {
+                Self::unschedule_prune(votes.expiry, src_id, nonce, prop_hash);
+                Self::schedule_prune(now + T::ProposalRetention::get(), src_id, nonce, prop_hash);
+            }
 
             match status {
-                ProposalStatus::Approved => Self::finalize_execution(src_id, nonce, prop),
-                ProposalStatus::Rejected => Self::cancel_execution(src_id, nonce),
-                _ => Ok(()),
+                ProposalStatus::Approved => Ok(Self::finalize_execution(src_id, nonce, prop_hash, prop)),
+                ProposalStatus::Rejected => {
+                    Self::reward_relayers(src_id, nonce, &votes.votes_against);
+                    Self::slash_relayers(&votes.votes_for);
+                    Self::cancel_execution(src_id, nonce).map(|_| 0)
+                }
+                _ => Ok(0),
             }
         } else {
             Err(Error::<T>::ProposalDoesNotExist)?
@@ -500,11 +2149,14 @@ impl<T: Trait> Module<T> {
     fn vote_for(
         who: T::AccountId,
         nonce: DepositNonce,
-        src_id: ChainId,
+        src_id: T::ChainId,
+        r_id: ResourceId,
+        prop_hash: T::Hash,
         prop: Box<T::Proposal>,
-    ) -> DispatchResult {
-        Self::commit_vote(who, nonce, src_id, prop.clone(), true)?;
-        Self::try_resolve_proposal(nonce, src_id, prop)
+        eligible: &mut Option<Vec<T::AccountId>>,
+    ) -> Result<Weight, DispatchError> {
+        Self::commit_vote(who, nonce, src_id, r_id, prop_hash, prop.clone(), true, eligible)?;
+        Self::try_resolve_proposal(nonce, src_id, prop_hash, prop)
     }
 
     /// Commits a vote against the proposal and cancels it if more than (relayers.len() - threshold)
@@ -512,45 +2164,237 @@ impl<T: Trait> Module<T> {
     fn vote_against(
         who: T::AccountId,
         nonce: DepositNonce,
-        src_id: ChainId,
+        src_id: T::ChainId,
+        r_id: ResourceId,
+        prop_hash: T::Hash,
         prop: Box<T::Proposal>,
-    ) -> DispatchResult {
-        Self::commit_vote(who, nonce, src_id, prop.clone(), false)?;
-        Self::try_resolve_proposal(nonce, src_id, prop)
+        eligible: &mut Option<Vec<T::AccountId>>,
+    ) -> Result<Weight, DispatchError> {
+        Self::commit_vote(who, nonce, src_id, r_id, prop_hash, prop.clone(), false, eligible)?;
+        Self::try_resolve_proposal(nonce, src_id, prop_hash, prop)
     }
 
-    /// Execute the proposal and signals the result as an event
+    /// Execute the proposal, record the outcome in its status and signal the result as an event.
+    ///
+    /// A failing call does not revert the votes that approved it. Only the changes made by the
+    /// call itself are rolled back, and the proposal is marked as `Failed`. The deposit nonce is
+    /// consumed either way, so a failed deposit can't be executed again through another proposal.
+    /// Relayers are rewarded and slashed once the outcome of the call is known. Returns the weight
+    /// used by the call.
     fn finalize_execution(
-        src_id: ChainId,
+        src_id: T::ChainId,
         nonce: DepositNonce,
+        prop_hash: T::Hash,
         call: Box<T::Proposal>,
-    ) -> DispatchResult {
+    ) -> Weight {
         Self::deposit_event(RawEvent::ProposalApproved(src_id, nonce));
-        call.dispatch(frame_system::RawOrigin::Signed(Self::account_id()).into())
-            .map(|_| ())
-            .map_err(|e| e.error)?;
-        Self::deposit_event(RawEvent::ProposalSucceeded(src_id, nonce));
-        Ok(())
+        // Marked outside the transaction so that a failed call still consumes the nonce, and
+        // before the dispatch so that the call can't reenter it
+        Self::mark_nonce_executed(src_id, nonce);
+        let info = call.get_dispatch_info();
+        let result = with_transaction(|| {
+            let res = call.dispatch(frame_system::RawOrigin::Signed(Self::account_id()).into());
+            if res.is_ok() {
+                TransactionOutcome::Commit(res)
+            } else {
+                TransactionOutcome::Rollback(res)
+            }
+        });
+
+        let (status, post_info) = match result {
+            Ok(post_info) => {
+                Self::deposit_event(RawEvent::ProposalSucceeded(src_id, nonce));
+                (ProposalStatus::Executed, post_info)
+            }
+            Err(e) => {
+                Self::deposit_event(RawEvent::ProposalFailed(src_id, nonce, e.error));
+                (ProposalStatus::Failed(e.error), e.post_info)
+            }
+        };
+        if let Some(mut votes) = <Votes<T>>::get((src_id, nonce), prop_hash) {
+            // A failing call turns the outcome against the relayers who voted for it
+            let (with_outcome, against_outcome) = match status {
+                ProposalStatus::Failed(_) => (&votes.votes_against, &votes.votes_for),
+                _ => (&votes.votes_for, &votes.votes_against),
+            };
+            Self::slash_relayers(against_outcome);
+            Self::reward_relayers(src_id, nonce, with_outcome);
+            votes.status = status;
+            <Votes<T>>::insert((src_id, nonce), prop_hash, votes);
+        }
+        post_info.calc_actual_weight(&info)
     }
 
     /// Cancels a proposal.
-    fn cancel_execution(src_id: ChainId, nonce: DepositNonce) -> DispatchResult {
+    fn cancel_execution(src_id: T::ChainId, nonce: DepositNonce) -> DispatchResult {
         Self::deposit_event(RawEvent::ProposalRejected(src_id, nonce));
         Ok(())
     }
 
+    // *** Fee, reward and slashing methods ***
+
+    /// Returns the funds of the reward pot that are not owed to any relayer yet.
+    /// The existential deposit is kept so that the pot is never reaped.
+    pub fn unallocated_rewards() -> BalanceOf<T> {
+        T::Currency::free_balance(&Self::reward_pot_id())
+            .saturating_sub(T::Currency::minimum_balance())
+            .saturating_sub(Self::total_pending_rewards())
+    }
+
+    /// Moves the fee for an outbound transfer to `dest_id` from `source` into the reward pot.
+    fn charge_fee(source: &T::AccountId, dest_id: T::ChainId) -> DispatchResult {
+        let fee = Self::bridge_fee(dest_id);
+        if fee.is_zero() {
+            return Ok(());
+        }
+        T::Currency::transfer(
+            source,
+            &Self::reward_pot_id(),
+            fee,
+            ExistenceRequirement::AllowDeath,
+        )
+    }
+
+    /// Splits the proposal reward among the relayers who voted with the outcome.
+    /// The reward is capped by the unallocated funds of the pot, and any remainder of the
+    /// split stays in it.
+    fn reward_relayers(src_id: T::ChainId, nonce: DepositNonce, relayers: &[T::AccountId]) {
+        if relayers.is_empty() {
+            return;
+        }
+        let count = BalanceOf::<T>::from(relayers.len() as u32);
+        let reward = sp_std::cmp::min(T::ProposalReward::get(), Self::unallocated_rewards());
+        let share = reward / count;
+        if share.is_zero() {
+            return;
+        }
+
+        for relayer in relayers {
+            <PendingRewards<T>>::mutate(relayer, |pending| *pending = pending.saturating_add(share));
+        }
+        <TotalPendingRewards<T>>::mutate(|total| *total = total.saturating_add(share * count));
+        Self::deposit_event(RawEvent::RelayersRewarded(src_id, nonce, share));
+    }
+
+    /// Slashes `SlashFraction` of the bond of each relayer and passes the funds to `OnSlash`.
+    /// Relayers without a bond are not affected.
+    fn slash_relayers(relayers: &[T::AccountId]) {
+        let fraction = T::SlashFraction::get();
+        if relayers.is_empty() || fraction.is_zero() {
+            return;
+        }
+
+        let mut slashed = NegativeImbalanceOf::<T>::zero();
+        for relayer in relayers {
+            let mut bond = match Self::bonds(relayer) {
+                Some(bond) => bond,
+                None => continue,
+            };
+            let amount = fraction * bond.amount;
+            if amount.is_zero() {
+                continue;
+            }
+
+            let (imbalance, _) = T::Currency::slash_reserved(relayer, amount);
+            bond.amount = bond.amount.saturating_sub(imbalance.peek());
+            <Bonds<T>>::insert(relayer, bond);
+            Self::deposit_event(RawEvent::RelayerSlashed(relayer.clone(), imbalance.peek()));
+            slashed.subsume(imbalance);
+        }
+        T::OnSlash::on_unbalanced(slashed);
+    }
+
+    // *** Proposal pruning methods ***
+
+    /// Queues a proposal for removal at block `at`, or at the next block if `at` has already
+    /// been processed.
+    fn schedule_prune(
+        at: T::BlockNumber,
+        src_id: T::ChainId,
+        nonce: DepositNonce,
+        prop_hash: T::Hash,
+    ) {
+        let next = <frame_system::Module<T>>::block_number() + One::one();
+        let at = if at < next { next } else { at };
+        if Self::prune_cursor().is_zero() {
+            <PruneCursor<T>>::put(next);
+        }
+        <PruneQueue<T>>::append(at, (src_id, nonce, prop_hash));
+    }
+
+    /// Removes a proposal from the prune queue entry for block `at`.
+    fn unschedule_prune(
+        at: T::BlockNumber,
+        src_id: T::ChainId,
+        nonce: DepositNonce,
+        prop_hash: T::Hash,
+    ) {
+        <PruneQueue<T>>::mutate(at, |queued| {
+            queued.retain(|(c, n, h)| !(*c == src_id && *n == nonce && *h == prop_hash))
+        });
+    }
+
+    /// Removes queued proposals that are due at or before `now`.
+    ///
+    /// At most `MaxPrunedPerBlock` units of work are performed, where visiting a block of the
+    /// queue and removing a proposal each count as one. Any remaining work is picked up by
+    /// subsequent blocks.
+    fn prune_proposals(now: T::BlockNumber) -> Weight {
+        let mut block = Self::prune_cursor();
+        if block.is_zero() {
+            return <T as Trait>::WeightInfo::prune_proposals(0);
+        }
+
+        let max_work = T::MaxPrunedPerBlock::get();
+        let mut budget = max_work;
+        while block <= now && budget > 0 {
+            let mut queued = <PruneQueue<T>>::take(block);
+            let remaining = queued.split_off(sp_std::cmp::min(queued.len(), budget as usize));
+            budget = budget.saturating_sub(sp_std::cmp::max(queued.len() as u32, 1));
+
+            for (src_id, nonce, prop_hash) in queued {
+                if <Votes<T>>::contains_key((src_id, nonce), prop_hash) {
+                    <Votes<T>>::remove((src_id, nonce), prop_hash);
+                    <ProposalPreimages<T>>::remove((src_id, nonce), prop_hash);
+                    Self::deposit_event(RawEvent::ProposalPruned(src_id, nonce));
+                }
+            }
+
+            if !remaining.is_empty() {
+                <PruneQueue<T>>::insert(block, remaining);
+                break;
+            }
+            block += One::one();
+        }
+        <PruneCursor<T>>::put(block);
+
+        <T as Trait>::WeightInfo::prune_proposals(max_work - budget)
+    }
+
     /// Initiates a transfer of a fungible asset out of the chain. This should be called by another pallet.
+    /// The bridge fee of the destination chain is charged to `source`, and the transfer is
+    /// recorded in `DepositRecords`. `resource_id` must be an enabled fungible resource that can be
+    /// transferred to `dest_id`.
     pub fn transfer_fungible(
-        dest_id: ChainId,
+        source: &T::AccountId,
+        dest_id: T::ChainId,
         resource_id: ResourceId,
         to: Vec<u8>,
         amount: U256,
     ) -> DispatchResult {
+        Self::ensure_chain_enabled(dest_id)?;
+        Self::ensure_not_paused(BridgeDirection::Outbound, dest_id, Some(resource_id))?;
+        let resource = Self::outbound_resource(dest_id, resource_id)?;
         ensure!(
-            Self::chain_whitelisted(dest_id),
-            Error::<T>::ChainNotWhitelisted
+            matches!(resource.kind, ResourceKind::Fungible { .. }),
+            Error::<T>::ResourceKindMismatch
         );
+        Self::ensure_valid_recipient(dest_id, &to)?;
+        let usage = Self::check_rate_limit(BridgeDirection::Outbound, dest_id, resource_id, amount)?;
+        Self::charge_fee(source, dest_id)?;
+        Self::note_rate_limit_usage(BridgeDirection::Outbound, dest_id, resource_id, usage);
         let nonce = Self::bump_nonce(dest_id);
+        Self::record_deposit(dest_id, nonce, resource_id, DepositPayload::Fungible(amount, to.clone()));
         Self::deposit_event(RawEvent::FungibleTransfer(
             dest_id,
             nonce,
@@ -562,18 +2406,36 @@ impl<T: Trait> Module<T> {
     }
 
     /// Initiates a transfer of a nonfungible asset out of the chain. This should be called by another pallet.
+    /// The bridge fee of the destination chain is charged to `source`, and the transfer is
+    /// recorded in `DepositRecords`. `resource_id` must be an enabled non-fungible resource that
+    /// can be transferred to `dest_id`.
     pub fn transfer_nonfungible(
-        dest_id: ChainId,
+        source: &T::AccountId,
+        dest_id: T::ChainId,
         resource_id: ResourceId,
         token_id: Vec<u8>,
         to: Vec<u8>,
         metadata: Vec<u8>,
     ) -> DispatchResult {
+        Self::ensure_chain_enabled(dest_id)?;
+        Self::ensure_not_paused(BridgeDirection::Outbound, dest_id, Some(resource_id))?;
+        let resource = Self::outbound_resource(dest_id, resource_id)?;
         ensure!(
-            Self::chain_whitelisted(dest_id),
-            Error::<T>::ChainNotWhitelisted
+            matches!(resource.kind, ResourceKind::NonFungible),
+            Error::<T>::ResourceKindMismatch
         );
+        Self::ensure_valid_recipient(dest_id, &to)?;
+        let usage =
+            Self::check_rate_limit(BridgeDirection::Outbound, dest_id, resource_id, U256::zero())?;
+        Self::charge_fee(source, dest_id)?;
+        Self::note_rate_limit_usage(BridgeDirection::Outbound, dest_id, resource_id, usage);
         let nonce = Self::bump_nonce(dest_id);
+        Self::record_deposit(
+            dest_id,
+            nonce,
+            resource_id,
+            DepositPayload::NonFungible(token_id.clone(), to.clone(), metadata.clone()),
+        );
         Self::deposit_event(RawEvent::NonFungibleTransfer(
             dest_id,
             nonce,
@@ -586,16 +2448,28 @@ impl<T: Trait> Module<T> {
     }
 
     /// Initiates a transfer of generic data out of the chain. This should be called by another pallet.
+    /// The bridge fee of the destination chain is charged to `source`, and the transfer is
+    /// recorded in `DepositRecords`. `resource_id` must be an enabled generic resource that can be
+    /// transferred to `dest_id`.
     pub fn transfer_generic(
-        dest_id: ChainId,
+        source: &T::AccountId,
+        dest_id: T::ChainId,
         resource_id: ResourceId,
         metadata: Vec<u8>,
     ) -> DispatchResult {
+        Self::ensure_chain_enabled(dest_id)?;
+        Self::ensure_not_paused(BridgeDirection::Outbound, dest_id, Some(resource_id))?;
+        let resource = Self::outbound_resource(dest_id, resource_id)?;
         ensure!(
-            Self::chain_whitelisted(dest_id),
-            Error::<T>::ChainNotWhitelisted
+            matches!(resource.kind, ResourceKind::Generic),
+            Error::<T>::ResourceKindMismatch
         );
+        let usage =
+            Self::check_rate_limit(BridgeDirection::Outbound, dest_id, resource_id, U256::zero())?;
+        Self::charge_fee(source, dest_id)?;
+        Self::note_rate_limit_usage(BridgeDirection::Outbound, dest_id, resource_id, usage);
         let nonce = Self::bump_nonce(dest_id);
+        Self::record_deposit(dest_id, nonce, resource_id, DepositPayload::Generic(metadata.clone()));
         Self::deposit_event(RawEvent::GenericTransfer(
             dest_id,
             nonce,
@@ -606,6 +2480,32 @@ impl<T: Trait> Module<T> {
     }
 }
 
+/// Registered relayers are the candidates of the DORR election
+impl<T: Trait> Contains<T::AccountId> for Module<T> {
+    fn sorted_members() -> Vec<T::AccountId> {
+        let mut relayers = Self::relayer_set();
+        relayers.sort();
+        relayers
+    }
+
+    fn contains(who: &T::AccountId) -> bool {
+        Self::is_relayer(who)
+    }
+
+    fn count() -> usize {
+        Self::relayer_count() as usize
+    }
+
+    /// Adds a relayer without requiring a bond
+    #[cfg(feature = "runtime-benchmarks")]
+    fn add(who: &T::AccountId) {
+        if !Self::is_relayer(who) {
+            <Relayers<T>>::insert(who, true);
+            <RelayerCount>::mutate(|i| *i += 1);
+        }
+    }
+}
+
 /// Simple ensure origin for the bridge account
 pub struct EnsureBridge<T>(sp_std::marker::PhantomData<T>);
 impl<T: Trait> EnsureOrigin<T::Origin> for EnsureBridge<T> {
@@ -617,4 +2517,9 @@ impl<T: Trait> EnsureOrigin<T::Origin> for EnsureBridge<T> {
             r => Err(T::Origin::from(r)),
         })
     }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn successful_origin() -> T::Origin {
+        T::Origin::from(system::RawOrigin::Signed(MODULE_ID.into_account()))
+    }
 }
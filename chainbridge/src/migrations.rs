@@ -0,0 +1,579 @@
+//! Storage migrations for the chainbridge pallet.
+//!
+//! Migrations are run from `on_runtime_upgrade` and tracked by `StorageVersion`, so each of
+//! them is applied once. Entries are only migrated if they are stored with the old layout, so
+//! a migration leaves storage that already has the new layout untouched. This matters for chains
+//! that were started without the genesis config of the pallet, and so without `StorageVersion`.
+
+use super::*;
+use codec::DecodeAll;
+use frame_support::{
+    debug,
+    storage::{migration::take_storage_value, unhashed},
+    Blake2_128Concat, StorageHasher, StorageMap, StorageValue, Twox128,
+};
+
+/// Applies the migrations the storage hasn't been migrated with yet. Chain IDs are widened if
+/// they are stored as `u8` and the runtime's `ChainId` is wider.
+pub fn migrate<T: Trait>() -> Weight {
+    let weight = T::DbWeight::get().reads(1);
+    let version = StorageVersion::get();
+    let current = Releases::current::<T>();
+    if version == current {
+        return weight;
+    }
+    if !version.has_narrow_chain_ids() && current.has_narrow_chain_ids() {
+        debug::error!("Chain IDs are stored as `u32`, they can't be migrated to a narrower `ChainId`");
+        return weight;
+    }
+
+    let weight = match version {
+        Releases::V1OpaqueKeys => weight.saturating_add(legacy::concat_keys::<T>()),
+        _ => weight,
+    };
+    // Proposals are re-keyed with the chain IDs they're stored with, before they're widened
+    let weight = match version {
+        Releases::V1OpaqueKeys | Releases::V2NarrowChainIds => {
+            weight.saturating_add(nonce_keys::key_votes_by_nonce::<T, u8>())
+        }
+        Releases::V3WideChainIds => weight.saturating_add(nonce_keys::key_votes_by_nonce::<T, u32>()),
+        _ => weight,
+    };
+    let weight = if version.has_narrow_chain_ids() && !current.has_narrow_chain_ids() {
+        weight.saturating_add(wide::widen_chain_ids::<T>())
+    } else {
+        weight
+    };
+    StorageVersion::put(current);
+    weight.saturating_add(T::DbWeight::get().writes(1))
+}
+
+/// Registers resources of the first release again, with the given kinds and call indices. Their
+/// IDs can't be recovered from the old keys, so the runtime has to list them. Destinations
+/// aren't restricted and the resources are enabled.
+///
+/// Must run in the runtime's `OnRuntimeUpgrade`, before the migration of the pallet, which
+/// removes the old resources that are left.
+pub fn migrate_legacy_resources<T: Trait>(resources: &[(ResourceId, ResourceKind, [u8; 2])]) -> Weight {
+    let mut migrated = 0u64;
+    for (id, kind, call_index) in resources {
+        let hash = sp_io::hashing::blake2_256(id);
+        if let Some(method) = take_storage_value::<Vec<u8>>(MODULE_PREFIX, b"Resources", &hash) {
+            let resource = Resource {
+                method,
+                kind: *kind,
+                destinations: Vec::new(),
+                enabled: true,
+                call_index: *call_index,
+            };
+            <Resources<T>>::insert(id, resource);
+            migrated += 1;
+        }
+    }
+    T::DbWeight::get().reads_writes(resources.len() as u64, migrated.saturating_mul(2))
+}
+
+/// Moves the relayers of the first release to the current layout. Their accounts can't be
+/// recovered from the old keys, so the runtime has to list them. The relayer count is left as is.
+///
+/// Must run in the runtime's `OnRuntimeUpgrade`, before the migration of the pallet, which
+/// removes the old relayers that are left if enough relayers remain to reach the threshold.
+pub fn migrate_legacy_relayers<T: Trait>(relayers: &[T::AccountId]) -> Weight {
+    let mut migrated = 0u64;
+    for who in relayers {
+        let hash = who.using_encoded(sp_io::hashing::blake2_256);
+        if let Some(relayer) = take_storage_value::<bool>(MODULE_PREFIX, b"Relayers", &hash) {
+            <Relayers<T>>::insert(who, relayer);
+            migrated += 1;
+        }
+    }
+    T::DbWeight::get().reads_writes(relayers.len() as u64, migrated.saturating_mul(2))
+}
+
+/// Marks the inbound deposit nonces of each listed source chain up to the given nonce as
+/// executed. Proposals that were voted on before the upgrade can't be looked up by their new
+/// keys, so the runtime has to list the nonces that were executed to keep them from being
+/// executed again.
+///
+/// Must run in the runtime's `OnRuntimeUpgrade` that upgrades from the first release.
+pub fn migrate_executed_nonces<T: Trait>(watermarks: &[(T::ChainId, DepositNonce)]) -> Weight {
+    let mut reads = 0u64;
+    let mut writes = 0u64;
+    for (src_id, nonce) in watermarks {
+        reads += 1;
+        let mut watermark = <ExecutedNonceWatermark<T>>::get(src_id);
+        if *nonce <= watermark {
+            continue;
+        }
+        let executed: Vec<DepositNonce> = <ExecutedNonces<T>>::iter_prefix(src_id)
+            .map(|(executed, _)| executed)
+            .filter(|executed| executed <= nonce)
+            .collect();
+        reads += executed.len() as u64;
+        for executed in executed {
+            <ExecutedNonces<T>>::remove(src_id, executed);
+            writes += 1;
+        }
+
+        watermark = *nonce;
+        while <ExecutedNonces<T>>::take(src_id, watermark + 1) {
+            watermark += 1;
+            reads += 1;
+            writes += 1;
+        }
+        <ExecutedNonceWatermark<T>>::insert(src_id, watermark);
+        writes += 1;
+    }
+    T::DbWeight::get().reads_writes(reads, writes)
+}
+
+/// Prefix of the storage items of the pallet
+const MODULE_PREFIX: &[u8] = b"ChainBridge";
+
+/// Prefix of the keys of a storage item of the pallet
+fn item_prefix(item: &[u8]) -> Vec<u8> {
+    [Twox128::hash(MODULE_PREFIX), Twox128::hash(item)].concat()
+}
+
+/// Returns the keys, without the item prefix, and the raw values of all entries of a storage item.
+fn raw_entries(item: &[u8]) -> Vec<(Vec<u8>, Vec<u8>)> {
+    let prefix = item_prefix(item);
+    let mut entries = Vec::new();
+    let mut previous_key = prefix.clone();
+    while let Some(key) = sp_io::storage::next_key(&previous_key).filter(|k| k.starts_with(&prefix)) {
+        if let Some(value) = sp_io::storage::get(&key) {
+            entries.push((key[prefix.len()..].to_vec(), value));
+        }
+        previous_key = key;
+    }
+    entries
+}
+
+/// Splits a key hashed with the concat hasher `H` off the start of `key`. Returns `None` if
+/// the hash doesn't match the key decoded as `K`, i.e. if the key isn't stored as a `K`.
+fn take_concat_key<K: Decode, H: StorageHasher>(key: &mut &[u8]) -> Option<K> {
+    let hash_len = H::hash(&[]).as_ref().len();
+    let mut input = key.get(hash_len..)?;
+    let decoded = K::decode(&mut input).ok()?;
+    let key_len = key.len() - input.len();
+    if H::hash(&key[hash_len..key_len]).as_ref() != &key[..key_len] {
+        return None;
+    }
+    *key = input;
+    Some(decoded)
+}
+
+/// Checks if `key` is a key of a `blake2_128_concat` map keyed by `K`
+fn is_concat_key<K: Decode>(key: &[u8]) -> bool {
+    let mut rest = key;
+    take_concat_key::<K, Blake2_128Concat>(&mut rest).is_some() && rest.is_empty()
+}
+
+/// Checks if `key` is a key of a double map keyed by `K1` and `K2`, both `blake2_128_concat`
+fn is_double_concat_key<K1: Decode, K2: Decode>(key: &[u8]) -> bool {
+    let mut rest = key;
+    take_concat_key::<K1, Blake2_128Concat>(&mut rest).is_some()
+        && take_concat_key::<K2, Blake2_128Concat>(&mut rest).is_some()
+        && rest.is_empty()
+}
+
+/// Migration of the proposals to keys that start with their source chain and nonce
+mod nonce_keys {
+    use super::*;
+
+    /// Checks if `key` is a key of the proposals that are keyed by a chain ID stored as `I`,
+    /// then by their nonce and hash
+    pub fn is_chain_keyed<T: Trait, I: Decode>(key: &[u8]) -> bool {
+        is_double_concat_key::<I, (DepositNonce, T::Hash)>(key)
+    }
+
+    /// Re-inserts the entries of a proposal map that are keyed by a chain ID stored as `I`, then
+    /// by their nonce and hash, with `insert`. Entries whose chain ID doesn't fit into `ChainId`
+    /// are kept. Returns the number of re-inserted entries.
+    fn rekey<T: Trait, I: Decode, V: Decode>(
+        item: &[u8],
+        mut insert: impl FnMut((T::ChainId, DepositNonce), T::Hash, V),
+    ) -> u64
+    where
+        T::ChainId: TryFrom<I>,
+    {
+        let prefix = item_prefix(item);
+        let mut count = 0;
+        for (key, value) in raw_entries(item) {
+            let mut rest = &key[..];
+            let chain_id = take_concat_key::<I, Blake2_128Concat>(&mut rest)
+                .and_then(|id| T::ChainId::try_from(id).ok());
+            let key2 = chain_id
+                .and_then(|_| take_concat_key::<(DepositNonce, T::Hash), Blake2_128Concat>(&mut rest));
+            if let (Some(chain_id), Some((nonce, prop_hash)), true, Ok(value)) =
+                (chain_id, key2, rest.is_empty(), V::decode_all(&value))
+            {
+                unhashed::kill(&[&prefix[..], &key[..]].concat());
+                insert((chain_id, nonce), prop_hash, value);
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// Re-keys the votes and preimages of the proposals by their source chain and nonce, then by
+    /// their hash. Their chain IDs are stored as `I`.
+    pub fn key_votes_by_nonce<T: Trait, I: Decode>() -> Weight
+    where
+        T::ChainId: TryFrom<I>,
+    {
+        let mut entries = rekey::<T, I, _>(
+            b"Votes",
+            |key1, prop_hash, votes: ProposalVotes<T::AccountId, T::BlockNumber>| {
+                <Votes<T>>::insert(key1, prop_hash, votes)
+            },
+        );
+        entries += rekey::<T, I, _>(b"ProposalPreimages", |key1, prop_hash, call: T::Proposal| {
+            <ProposalPreimages<T>>::insert(key1, prop_hash, call)
+        });
+        T::DbWeight::get().reads_writes(entries, entries.saturating_mul(2))
+    }
+}
+
+/// Migration from the `opaque_blake2_256` keys of the first release of the pallet
+mod legacy {
+    use super::*;
+    use sp_io::hashing::blake2_256;
+
+    /// `ProposalVotes` of the first release
+    #[derive(Decode)]
+    struct OldProposalVotes<AccountId, BlockNumber> {
+        #[allow(dead_code)]
+        votes_for: Vec<AccountId>,
+        #[allow(dead_code)]
+        votes_against: Vec<AccountId>,
+        #[allow(dead_code)]
+        status: u8,
+        #[allow(dead_code)]
+        expiry: BlockNumber,
+    }
+
+    /// Returns the keys and values of the `Votes` entries stored by the first release
+    fn old_votes<T: Trait>() -> Vec<(Vec<u8>, OldProposalVotes<T::AccountId, T::BlockNumber>)> {
+        raw_entries(b"Votes")
+            .into_iter()
+            .filter(|(key, _)| {
+                // Proposals stored with the later layouts are kept
+                !nonce_keys::is_chain_keyed::<T, u8>(key)
+                    && !is_double_concat_key::<(T::ChainId, DepositNonce), T::Hash>(key)
+            })
+            .filter_map(|(key, value)| Some((key, OldProposalVotes::decode_all(&value).ok()?)))
+            .collect()
+    }
+
+    /// Re-keys the maps of the first release with `blake2_128_concat` and recounts the relayers.
+    ///
+    /// The chain nonces are recovered by hashing every `u8` chain ID. Relayer accounts can't be
+    /// recovered from their hashes, so the runtime must provide them with
+    /// `migrate_legacy_relayers`. The relayers that weren't migrated that way are removed, unless
+    /// that would leave fewer relayers than the threshold. Then they are kept, along with the
+    /// relayer count, until a later upgrade migrates them. Migrated relayers don't have a bond
+    /// until they `bond`.
+    ///
+    /// Proposals are keyed by the hash of their call now, which can't be derived from the hash of
+    /// the old key, nor their nonces, so their votes are removed. Relayers must vote again on the
+    /// proposals that were still initiated. The runtime must mark the nonces of the proposals
+    /// that were executed with `migrate_executed_nonces`, so that they aren't executed again.
+    ///
+    /// Resources are stored with a kind and a call index now, which the runtime must provide with
+    /// `migrate_legacy_resources`. Resources that weren't migrated that way are removed.
+    pub fn concat_keys<T: Trait>() -> Weight {
+        let mut reads = 0u64;
+        let mut writes = 0u64;
+
+        for id in 0..=u8::max_value() {
+            let hash = blake2_256(&id.encode());
+            if let Some(nonce) = take_storage_value::<DepositNonce>(MODULE_PREFIX, b"ChainNonces", &hash) {
+                <ChainNonces<T>>::insert(T::ChainId::from(id), nonce);
+                writes += 2;
+            }
+        }
+        reads += u64::from(u8::max_value()) + 1;
+
+        let votes_prefix = item_prefix(b"Votes");
+        let mut removed_votes = 0u32;
+        for (key, _) in old_votes::<T>() {
+            unhashed::kill(&[&votes_prefix[..], &key[..]].concat());
+            removed_votes += 1;
+            reads += 1;
+            writes += 1;
+        }
+        if removed_votes > 0 {
+            debug::warn!(
+                "Removed {} proposals that must be voted on again, their executed nonces must be \
+                 migrated by the runtime",
+                removed_votes
+            );
+        }
+
+        let mut migrated = 0u32;
+        let mut unmigrated = Vec::new();
+        for (key, value) in raw_entries(b"Relayers") {
+            reads += 1;
+            if !is_concat_key::<T::AccountId>(&key) {
+                unmigrated.push(key);
+            } else if bool::decode_all(&value) == Ok(true) {
+                migrated += 1;
+            }
+        }
+        if !unmigrated.is_empty() {
+            reads += 1;
+            if migrated >= <RelayerThreshold>::get() {
+                let prefix = item_prefix(b"Relayers");
+                for key in &unmigrated {
+                    unhashed::kill(&[&prefix[..], &key[..]].concat());
+                }
+                <RelayerCount>::put(migrated);
+                writes += unmigrated.len() as u64 + 1;
+                debug::warn!("Removed {} relayers that weren't migrated", unmigrated.len());
+            } else {
+                debug::error!(
+                    "Kept {} relayers that weren't migrated, {} relayers can't reach the threshold",
+                    unmigrated.len(),
+                    migrated
+                );
+            }
+        } else {
+            <RelayerCount>::put(migrated);
+            writes += 1;
+        }
+
+        let prefix = item_prefix(b"Resources");
+        let mut removed_resources = 0u32;
+        for (key, _) in raw_entries(b"Resources") {
+            reads += 1;
+            if !is_concat_key::<ResourceId>(&key) {
+                unhashed::kill(&[&prefix[..], &key[..]].concat());
+                removed_resources += 1;
+                writes += 1;
+            }
+        }
+        if removed_resources > 0 {
+            debug::warn!("Removed {} resources that weren't registered again", removed_resources);
+        }
+
+        T::DbWeight::get().reads_writes(reads, writes)
+    }
+}
+
+/// Migration from `u8` to `u32` chain IDs
+mod wide {
+    use super::*;
+    use frame_support::Twox64Concat;
+
+    /// Converts the values of a storage item that are stored as `O`, keeping their keys.
+    /// Returns the number of converted values.
+    fn translate_values<O: Decode, V: Encode>(item: &[u8], convert: impl Fn(O) -> V) -> u64 {
+        let prefix = item_prefix(item);
+        let mut count = 0;
+        for (key, value) in raw_entries(item) {
+            if let Ok(old) = O::decode_all(&value) {
+                unhashed::put(&[&prefix[..], &key[..]].concat(), &convert(old));
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// `PauseScope` with a `u8` chain ID
+    #[derive(Decode)]
+    enum OldPauseScope {
+        Bridge,
+        Direction(BridgeDirection),
+        Chain(u8),
+        Resource(ResourceId),
+    }
+
+    impl<ChainId: From<u8>> From<OldPauseScope> for PauseScope<ChainId> {
+        fn from(scope: OldPauseScope) -> Self {
+            match scope {
+                OldPauseScope::Bridge => PauseScope::Bridge,
+                OldPauseScope::Direction(direction) => PauseScope::Direction(direction),
+                OldPauseScope::Chain(id) => PauseScope::Chain(id.into()),
+                OldPauseScope::Resource(id) => PauseScope::Resource(id),
+            }
+        }
+    }
+
+    /// `Resource` with `u8` destination chain IDs
+    #[derive(Decode)]
+    struct OldResource {
+        method: Vec<u8>,
+        kind: ResourceKind,
+        destinations: Vec<u8>,
+        enabled: bool,
+        call_index: [u8; 2],
+    }
+
+    impl<ChainId: From<u8>> From<OldResource> for Resource<ChainId> {
+        fn from(resource: OldResource) -> Self {
+            Resource {
+                method: resource.method,
+                kind: resource.kind,
+                destinations: resource.destinations.into_iter().map(Into::into).collect(),
+                enabled: resource.enabled,
+                call_index: resource.call_index,
+            }
+        }
+    }
+
+    /// `AdminAction` with a `u8` chain ID
+    #[derive(Decode)]
+    enum OldAdminAction<AccountId> {
+        SetThreshold(u32),
+        AddRelayer(AccountId),
+        RemoveRelayer(AccountId),
+        SetThresholdFraction(Option<Perbill>),
+        SetChainThreshold(u8, Option<u32>),
+    }
+
+    impl<AccountId, ChainId: From<u8>> From<OldAdminAction<AccountId>> for AdminAction<AccountId, ChainId> {
+        fn from(action: OldAdminAction<AccountId>) -> Self {
+            match action {
+                OldAdminAction::SetThreshold(threshold) => AdminAction::SetThreshold(threshold),
+                OldAdminAction::AddRelayer(relayer) => AdminAction::AddRelayer(relayer),
+                OldAdminAction::RemoveRelayer(relayer) => AdminAction::RemoveRelayer(relayer),
+                OldAdminAction::SetThresholdFraction(fraction) => {
+                    AdminAction::SetThresholdFraction(fraction)
+                }
+                OldAdminAction::SetChainThreshold(id, threshold) => {
+                    AdminAction::SetChainThreshold(id.into(), threshold)
+                }
+            }
+        }
+    }
+
+    /// Re-inserts the entries of a `blake2_128_concat` map that are keyed by an `OldK`, with
+    /// `insert`. Returns the number of entries.
+    fn migrate_map<OldK: Decode, V: Decode>(item: &[u8], mut insert: impl FnMut(OldK, V)) -> u64 {
+        let prefix = item_prefix(item);
+        let mut count = 0;
+        for (key, value) in raw_entries(item) {
+            let mut rest = &key[..];
+            let old_key = take_concat_key::<OldK, Blake2_128Concat>(&mut rest);
+            if let (Some(old_key), true, Ok(value)) = (old_key, rest.is_empty(), V::decode_all(&value)) {
+                unhashed::kill(&[&prefix[..], &key[..]].concat());
+                insert(old_key, value);
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// Re-inserts the entries of a double map whose first key is `blake2_128_concat` hashed and
+    /// holds a `u8` chain ID, with `insert`. Returns the number of entries.
+    fn migrate_double_map<OldK1: Decode, K2: Decode, H2: StorageHasher, V: Decode>(
+        item: &[u8],
+        mut insert: impl FnMut(OldK1, K2, V),
+    ) -> u64 {
+        let prefix = item_prefix(item);
+        let mut count = 0;
+        for (key, value) in raw_entries(item) {
+            let mut rest = &key[..];
+            let key1 = take_concat_key::<OldK1, Blake2_128Concat>(&mut rest);
+            let key2 = key1.as_ref().and_then(|_| take_concat_key::<K2, H2>(&mut rest));
+            if let (Some(key1), Some(key2), true, Ok(value)) =
+                (key1, key2, rest.is_empty(), V::decode_all(&value))
+            {
+                unhashed::kill(&[&prefix[..], &key[..]].concat());
+                insert(key1, key2, value);
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// Migrates the storage from `u8` to `u32` chain IDs. Items keyed by chain IDs are re-keyed,
+    /// and chain IDs stored in values are widened.
+    pub fn widen_chain_ids<T: Trait>() -> Weight {
+        let mut entries = 0u64;
+
+        entries += migrate_map(b"ChainNonces", |id: u8, nonce: DepositNonce| {
+            <ChainNonces<T>>::insert(T::ChainId::from(id), nonce)
+        });
+        entries += migrate_double_map::<_, _, Blake2_128Concat, _>(
+            b"Votes",
+            |(id, nonce): (u8, DepositNonce),
+             prop_hash: T::Hash,
+             votes: ProposalVotes<T::AccountId, T::BlockNumber>| {
+                <Votes<T>>::insert((T::ChainId::from(id), nonce), prop_hash, votes)
+            },
+        );
+        entries += migrate_double_map::<_, _, Blake2_128Concat, _>(
+            b"ProposalPreimages",
+            |(id, nonce): (u8, DepositNonce), prop_hash: T::Hash, call: T::Proposal| {
+                <ProposalPreimages<T>>::insert((T::ChainId::from(id), nonce), prop_hash, call)
+            },
+        );
+        entries += migrate_map(b"ExecutedNonceWatermark", |id: u8, nonce: DepositNonce| {
+            <ExecutedNonceWatermark<T>>::insert(T::ChainId::from(id), nonce)
+        });
+        entries += migrate_double_map::<_, _, Twox64Concat, _>(
+            b"ExecutedNonces",
+            |id: u8, nonce: DepositNonce, executed: bool| {
+                <ExecutedNonces<T>>::insert(T::ChainId::from(id), nonce, executed)
+            },
+        );
+        entries += migrate_map(b"Paused", |scope: OldPauseScope, paused: bool| {
+            <Paused<T>>::insert(PauseScopeOf::<T>::from(scope), paused)
+        });
+        entries += migrate_map(b"DisabledChains", |id: u8, disabled: bool| {
+            <DisabledChains<T>>::insert(T::ChainId::from(id), disabled)
+        });
+        entries += migrate_map(b"ChainConfigs", |id: u8, config: ChainConfig<T::BlockNumber>| {
+            <ChainConfigs<T>>::insert(T::ChainId::from(id), config)
+        });
+        entries += migrate_map(b"ChainThresholds", |id: u8, threshold: u32| {
+            <ChainThresholds<T>>::insert(T::ChainId::from(id), threshold)
+        });
+        entries += migrate_map(
+            b"RateLimits",
+            |(direction, id, r_id): (BridgeDirection, u8, ResourceId), limit: RateLimit<T::BlockNumber>| {
+                <RateLimits<T>>::insert((direction, T::ChainId::from(id), r_id), limit)
+            },
+        );
+        entries += migrate_map(
+            b"RateLimitUsage",
+            |(direction, id, r_id): (BridgeDirection, u8, ResourceId),
+             usage: Vec<RateLimitBucket<T::BlockNumber>>| {
+                <RateLimitUsage<T>>::insert((direction, T::ChainId::from(id), r_id), usage)
+            },
+        );
+        entries += migrate_double_map::<_, _, Twox64Concat, _>(
+            b"DepositRecords",
+            |id: u8, nonce: DepositNonce, record: DepositRecord<T::BlockNumber>| {
+                <DepositRecords<T>>::insert(T::ChainId::from(id), nonce, record)
+            },
+        );
+        entries += migrate_map(b"BridgeFees", |id: u8, fee: BalanceOf<T>| {
+            <BridgeFees<T>>::insert(T::ChainId::from(id), fee)
+        });
+
+        // Values holding chain IDs keep their keys
+        let mut translated = translate_values(
+            b"PruneQueue",
+            |queued: Vec<(u8, DepositNonce, T::Hash)>| -> Vec<(T::ChainId, DepositNonce, T::Hash)> {
+                queued.into_iter().map(|(id, nonce, hash)| (id.into(), nonce, hash)).collect()
+            },
+        );
+        translated += translate_values(b"Resources", |resource: OldResource| {
+            ResourceOf::<T>::from(resource)
+        });
+        translated += translate_values(
+            b"ScheduledAdminActions",
+            |(at, action): (T::BlockNumber, OldAdminAction<T::AccountId>)| {
+                (at, AdminActionOf::<T>::from(action))
+            },
+        );
+
+        T::DbWeight::get().reads_writes(
+            entries.saturating_add(translated),
+            entries.saturating_mul(2).saturating_add(translated),
+        )
+    }
+}
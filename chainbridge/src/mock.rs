@@ -4,7 +4,7 @@ use super::*;
 
 use frame_support::{assert_ok, ord_parameter_types, parameter_types, weights::Weight};
 use frame_system::{self as system};
-use sp_core::H256;
+use sp_core::{sr25519, H256};
 use sp_runtime::{
     testing::Header,
     traits::{AccountIdConversion, BlakeTwo256, Block as BlockT, IdentityLookup},
@@ -15,6 +15,7 @@ use crate::{self as bridge, Trait};
 use crate::sp_api_hidden_includes_decl_storage::hidden_include::traits::OnInitialize;
 use crate::sp_api_hidden_includes_decl_storage::hidden_include::traits::OnFinalize;
 pub use pallet_balances as balances;
+use std::cell::RefCell;
 
 parameter_types! {
     pub const BlockHashCount: u64 = 250;
@@ -52,10 +53,6 @@ impl frame_system::Trait for Test {
     type PalletInfo = ();
 }
 
-parameter_types! {
-    pub const ExistentialDeposit: u64 = 1;
-}
-
 ord_parameter_types! {
     pub const One: u64 = 1;
 }
@@ -75,26 +72,113 @@ pub type Randomness = pallet_randomness_collective_flip::Module<Test>;
 parameter_types! {
     pub const MaxActiveRelayers: u8 = 3;
     pub const EpochDuration: u8 = 10;
+    pub const VrfResultLifetime: u32 = 2;
 }
 
 impl dorr::Trait for Test {
     type Event = Event;
     type MaxActiveRelayers = MaxActiveRelayers;
     type EpochDuration = EpochDuration;
+    type VrfResultLifetime = VrfResultLifetime;
     type RandomnessSource = Randomness;
+    type EligibleCandidates = Bridge;
+    type WeightInfo = ();
 }
 
 parameter_types! {
-    pub const TestChainId: u8 = 5;
+    pub const TestChainId: u32 = 5;
+    pub const TestChainIdFormat: ChainIdFormat = ChainIdFormat::Narrow;
     pub const ProposalLifetime: u64 = 50;
+    pub const ProposalRetention: u64 = 20;
+    pub const MaxPrunedPerBlock: u32 = 2;
+    pub const MaxVotesPerBatch: u32 = 10;
+    pub const DepositRetention: u64 = 3;
+    pub const ProposalReward: u64 = 10;
+    pub const UnbondingDelay: u64 = 10;
+    pub const SlashFraction: Perbill = Perbill::from_percent(10);
+}
+
+thread_local! {
+    static RELAYER_BOND: RefCell<u64> = RefCell::new(0);
+    static ADMIN_DELAY: RefCell<u64> = RefCell::new(0);
+    static STORE_PREIMAGES: RefCell<bool> = RefCell::new(true);
+    static EXISTENTIAL_DEPOSIT: RefCell<u64> = RefCell::new(1);
+}
+
+// Existential deposit, one unless a test raises it
+pub struct ExistentialDeposit;
+impl ExistentialDeposit {
+    pub fn set(deposit: u64) {
+        EXISTENTIAL_DEPOSIT.with(|v| *v.borrow_mut() = deposit);
+    }
+}
+impl Get<u64> for ExistentialDeposit {
+    fn get() -> u64 {
+        EXISTENTIAL_DEPOSIT.with(|v| *v.borrow())
+    }
+}
+
+// Relayer bond, disabled unless a test sets it
+pub struct RelayerBond;
+impl RelayerBond {
+    pub fn set(bond: u64) {
+        RELAYER_BOND.with(|v| *v.borrow_mut() = bond);
+    }
+}
+impl Get<u64> for RelayerBond {
+    fn get() -> u64 {
+        RELAYER_BOND.with(|v| *v.borrow())
+    }
+}
+
+// Admin delay, changes apply immediately unless a test sets it
+pub struct AdminDelay;
+impl AdminDelay {
+    pub fn set(delay: u64) {
+        ADMIN_DELAY.with(|v| *v.borrow_mut() = delay);
+    }
+}
+impl Get<u64> for AdminDelay {
+    fn get() -> u64 {
+        ADMIN_DELAY.with(|v| *v.borrow())
+    }
+}
+
+// Preimage store, enabled unless a test disables it
+pub struct StorePreimages;
+impl StorePreimages {
+    pub fn set(store: bool) {
+        STORE_PREIMAGES.with(|v| *v.borrow_mut() = store);
+    }
+}
+impl Get<bool> for StorePreimages {
+    fn get() -> bool {
+        STORE_PREIMAGES.with(|v| *v.borrow())
+    }
 }
 
 impl Trait for Test {
     type Event = Event;
     type AdminOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type VetoOrigin = frame_system::EnsureSignedBy<One, u64>;
+    type AdminDelay = AdminDelay;
     type Proposal = Call;
-    type ChainId = TestChainId;
+    type ChainId = u32;
+    type ChainIdentity = TestChainId;
+    type ChainIdFormat = TestChainIdFormat;
     type ProposalLifetime = ProposalLifetime;
+    type ProposalRetention = ProposalRetention;
+    type MaxPrunedPerBlock = MaxPrunedPerBlock;
+    type StorePreimages = StorePreimages;
+    type MaxVotesPerBatch = MaxVotesPerBatch;
+    type DepositRetention = DepositRetention;
+    type Currency = Balances;
+    type ProposalReward = ProposalReward;
+    type RelayerBond = RelayerBond;
+    type UnbondingDelay = UnbondingDelay;
+    type SlashFraction = SlashFraction;
+    type OnSlash = ();
+    type WeightInfo = ();
 }
 
 pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
@@ -117,10 +201,14 @@ frame_support::construct_runtime!(
 pub const RELAYER_A: u64 = 0x2;
 pub const RELAYER_B: u64 = 0x3;
 pub const RELAYER_C: u64 = 0x4;
+pub const USER: u64 = 0x5;
 pub const ENDOWED_BALANCE: u64 = 100_000_000;
 pub const TEST_THRESHOLD: u32 = 2;
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
+    RelayerBond::set(0);
+    AdminDelay::set(0);
+    ExistentialDeposit::set(1);
     let bridge_id = ModuleId(*b"cb/bridg").into_account();
     let mut t = frame_system::GenesisConfig::default()
         .build_storage::<Test>()
@@ -135,10 +223,22 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
     ext
 }
 
+// An enabled resource of the given kind, accepting proposals that make the same call as `call`
+pub fn make_resource(kind: ResourceKind, method: &[u8], call: &Call) -> ResourceOf<Test> {
+    let call = call.encode();
+    Resource {
+        method: method.to_vec(),
+        kind,
+        destinations: vec![],
+        enabled: true,
+        call_index: [call[0], call[1]],
+    }
+}
+
 pub fn new_test_ext_initialized(
-    src_id: ChainId,
+    src_id: u32,
     r_id: ResourceId,
-    resource: Vec<u8>,
+    resource: ResourceOf<Test>,
 ) -> sp_io::TestExternalities {
     let mut t = new_test_ext();
     t.execute_with(|| {
@@ -164,9 +264,70 @@ pub fn run_to_block(n: u64) {
 			System::on_finalize(System::block_number());
 			System::set_block_number(System::block_number() + 1);
 			System::on_initialize(System::block_number());
+			Dorr::on_initialize(System::block_number());
 	}
 }
 
+// Deterministic DORR key pair of a test relayer
+pub fn relayer_keypair(relayer: u64) -> schnorrkel::Keypair {
+    schnorrkel::MiniSecretKey::from_bytes(&[relayer as u8; 32])
+        .unwrap()
+        .expand_to_keypair(schnorrkel::ExpansionMode::Ed25519)
+}
+
+// Registers the DORR key of the relayer, proving possession of it in the current epoch
+pub fn register_dorr_key(relayer: u64) {
+    let keypair = relayer_keypair(relayer);
+    let payload = Dorr::pk_ownership_payload(&relayer, Dorr::get_current_epoch());
+    let signature = sr25519::Signature::from_raw(keypair.sign_simple(b"substrate", &payload).to_bytes());
+    assert_ok!(Dorr::set_pk(
+        Origin::signed(relayer),
+        keypair.public.to_bytes().to_vec(),
+        signature
+    ));
+}
+
+// Submits the VRF result of the relayer over the randomness of the current epoch, for the election
+// of the next epoch
+pub fn submit_vrf_result(relayer: u64) {
+    let keypair = relayer_keypair(relayer);
+    let epoch = Dorr::get_current_epoch();
+    let context = schnorrkel::signing_context(&Vec::<u8>::new());
+    let (inout, proof, _) = keypair.vrf_sign(context.bytes(Dorr::get_public_randomness(epoch).as_ref()));
+    assert_ok!(Dorr::set_vrf_results(
+        Origin::signed(relayer),
+        inout.to_preout().to_bytes().to_vec(),
+        proof.to_bytes().to_vec()
+    ));
+}
+
+// Registers the DORR keys of the relayers, submits their VRF results in the following epoch, and
+// runs to the epoch they're elected for. Relayers activated before are no longer active.
+pub fn activate(relayers: &[u64]) {
+    for relayer in relayers {
+        register_dorr_key(*relayer);
+    }
+
+    run_to_next_epoch();
+
+    for relayer in relayers {
+        submit_vrf_result(*relayer);
+    }
+
+    run_to_next_epoch();
+}
+
+// Runs to the first block of the next epoch
+pub fn run_to_next_epoch() {
+    let epoch_duration = EpochDuration::get() as u64;
+    run_to_block(Dorr::get_current_epoch() * epoch_duration + 1);
+}
+
+// Makes every test relayer active
+pub fn activate_relayers() {
+    activate(&[RELAYER_A, RELAYER_B, RELAYER_C]);
+}
+
 // Checks events against the latest. A contiguous set of events must be provided. They must
 // include the most recent event, but do not have to include every past event.
 pub fn assert_events(mut expected: Vec<Event>) {
@@ -182,3 +343,11 @@ pub fn assert_events(mut expected: Vec<Event>) {
         assert_eq!(next, evt.into(), "Events don't match (actual,expected)");
     }
 }
+
+// A module error as it's read back from the stored events, which don't keep the error message
+pub fn event_error(error: Error<Test>) -> DispatchError {
+    match error.into() {
+        DispatchError::Module { index, error, .. } => DispatchError::Module { index, error, message: None },
+        other => other,
+    }
+}
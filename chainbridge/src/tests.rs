@@ -1,13 +1,19 @@
 #![cfg(test)]
 
 use super::mock::{
-    assert_events, new_test_ext, Balances, Bridge, Call, Event, Origin, ProposalLifetime, Dorr, run_to_block,
-    Test, TestChainId, ENDOWED_BALANCE, RELAYER_A, RELAYER_B, RELAYER_C, TEST_THRESHOLD,
+    activate, activate_relayers, assert_events, event_error, make_resource, new_test_ext, relayer_keypair,
+    Balances, Bridge, Call, Event, Origin, ProposalLifetime, ProposalRetention, Dorr, ExistentialDeposit, RelayerBond,
+    run_to_block, StorePreimages, System, Test, TestChainId, UnbondingDelay, AdminDelay, MaxVotesPerBatch, ENDOWED_BALANCE,
+    RELAYER_A, RELAYER_B, RELAYER_C, TEST_THRESHOLD, USER,
 };
 use super::*;
 use crate::mock::new_test_ext_initialized;
-use frame_support::{assert_noop, assert_ok};
-use rustc_hex::{FromHex};
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{Contains, OnInitialize},
+};
+use sp_core::{sr25519, H256};
+use sp_runtime::traits::BlakeTwo256;
 
 #[test]
 fn derive_ids() {
@@ -24,6 +30,46 @@ fn derive_ids() {
     assert_eq!(r_id, expected);
 }
 
+#[test]
+fn derive_wide_ids() {
+    let chain: u32 = 0x0102_0304;
+    let id = [0xaa; 30];
+    let r_id = derive_wide_resource_id(chain, &id);
+    let mut expected = [0xaa; 32];
+    expected[28..].copy_from_slice(&[0x01, 0x02, 0x03, 0x04]);
+    assert_eq!(r_id, expected);
+
+    let r_id = derive_wide_resource_id(7, &[0x21, 0x60]);
+    let mut expected = [0; 32];
+    expected[26] = 0x21;
+    expected[27] = 0x60;
+    expected[31] = 7;
+    assert_eq!(r_id, expected);
+}
+
+#[test]
+fn chain_id_formats() {
+    assert_eq!(ChainIdFormat::Narrow.max_chain_id(), 255);
+    assert_eq!(
+        ChainIdFormat::Narrow.derive_resource_id(255, b"remark"),
+        Some(derive_resource_id(255, b"remark"))
+    );
+}
+
+#[test]
+fn wide_chain_id_formats() {
+    assert_eq!(ChainIdFormat::Wide.max_chain_id(), u32::max_value());
+    assert_eq!(
+        ChainIdFormat::Wide.derive_resource_id(255, b"remark"),
+        Some(derive_wide_resource_id(255, b"remark"))
+    );
+    assert_eq!(ChainIdFormat::Narrow.derive_resource_id(256, b"remark"), None);
+    assert_eq!(
+        ChainIdFormat::Wide.derive_resource_id(256, b"remark"),
+        Some(derive_wide_resource_id(256, b"remark"))
+    );
+}
+
 #[test]
 fn complete_proposal_approved() {
     let mut prop = ProposalVotes {
@@ -31,6 +77,8 @@ fn complete_proposal_approved() {
         votes_against: vec![3],
         status: ProposalStatus::Initiated,
         expiry: ProposalLifetime::get(),
+        voters: vec![1, 2, 3],
+        resource_id: [0; 32],
     };
 
     prop.try_to_complete(2, 3);
@@ -44,6 +92,8 @@ fn complete_proposal_rejected() {
         votes_against: vec![2, 3],
         status: ProposalStatus::Initiated,
         expiry: ProposalLifetime::get(),
+        voters: vec![1, 2, 3],
+        resource_id: [0; 32],
     };
 
     prop.try_to_complete(2, 3);
@@ -57,6 +107,8 @@ fn complete_proposal_bad_threshold() {
         votes_against: vec![],
         status: ProposalStatus::Initiated,
         expiry: ProposalLifetime::get(),
+        voters: vec![1, 2],
+        resource_id: [0; 32],
     };
 
     prop.try_to_complete(3, 2);
@@ -67,6 +119,8 @@ fn complete_proposal_bad_threshold() {
         votes_against: vec![1, 2],
         status: ProposalStatus::Initiated,
         expiry: ProposalLifetime::get(),
+        voters: vec![1, 2],
+        resource_id: [0; 32],
     };
 
     prop.try_to_complete(3, 2);
@@ -77,14 +131,25 @@ fn complete_proposal_bad_threshold() {
 fn setup_resources() {
     new_test_ext().execute_with(|| {
         let id: ResourceId = [1; 32];
-        let method = "Pallet.do_something".as_bytes().to_vec();
-        let method2 = "Pallet.do_somethingElse".as_bytes().to_vec();
+        let resource = Resource {
+            method: "Pallet.do_something".as_bytes().to_vec(),
+            kind: ResourceKind::Fungible { decimals: 18 },
+            destinations: vec![2],
+            enabled: true,
+            call_index: [1, 0],
+        };
+        let resource2 = Resource {
+            method: "Pallet.do_somethingElse".as_bytes().to_vec(),
+            kind: ResourceKind::Generic,
+            enabled: false,
+            ..resource.clone()
+        };
 
-        assert_ok!(Bridge::set_resource(Origin::root(), id, method.clone()));
-        assert_eq!(Bridge::resources(id), Some(method));
+        assert_ok!(Bridge::set_resource(Origin::root(), id, resource.clone()));
+        assert_eq!(Bridge::resources(id), Some(resource));
 
-        assert_ok!(Bridge::set_resource(Origin::root(), id, method2.clone()));
-        assert_eq!(Bridge::resources(id), Some(method2));
+        assert_ok!(Bridge::set_resource(Origin::root(), id, resource2.clone()));
+        assert_eq!(Bridge::resources(id), Some(resource2));
 
         assert_ok!(Bridge::remove_resource(Origin::root(), id));
         assert_eq!(Bridge::resources(id), None);
@@ -101,6 +166,11 @@ fn whitelist_chain() {
             Bridge::whitelist_chain(Origin::root(), TestChainId::get()),
             Error::<Test>::InvalidChainId
         );
+        // The mock uses `u32` chain IDs, but the narrow format
+        assert_noop!(
+            Bridge::whitelist_chain(Origin::root(), 256),
+            Error::<Test>::InvalidChainId
+        );
 
         assert_events(vec![Event::bridge(RawEvent::ChainWhitelisted(0))]);
     })
@@ -130,14 +200,20 @@ fn asset_transfer_success() {
         let dest_id = 2;
         let to = vec![2];
         let resource_id = [1; 32];
+        let nft_resource_id = [2; 32];
+        let generic_resource_id = [3; 32];
         let metadata = vec![];
         let amount = 100;
         let token_id = vec![1, 2, 3, 4];
 
         assert_ok!(Bridge::set_threshold(Origin::root(), TEST_THRESHOLD,));
+        register_outbound_resource(resource_id, ResourceKind::Fungible { decimals: 18 });
+        register_outbound_resource(nft_resource_id, ResourceKind::NonFungible);
+        register_outbound_resource(generic_resource_id, ResourceKind::Generic);
 
         assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_id.clone()));
         assert_ok!(Bridge::transfer_fungible(
+            &USER,
             dest_id.clone(),
             resource_id.clone(),
             to.clone(),
@@ -155,8 +231,9 @@ fn asset_transfer_success() {
         ]);
 
         assert_ok!(Bridge::transfer_nonfungible(
+            &USER,
             dest_id.clone(),
-            resource_id.clone(),
+            nft_resource_id.clone(),
             token_id.clone(),
             to.clone(),
             metadata.clone()
@@ -164,21 +241,22 @@ fn asset_transfer_success() {
         assert_events(vec![Event::bridge(RawEvent::NonFungibleTransfer(
             dest_id.clone(),
             2,
-            resource_id.clone(),
+            nft_resource_id.clone(),
             token_id,
             to.clone(),
             metadata.clone(),
         ))]);
 
         assert_ok!(Bridge::transfer_generic(
+            &USER,
             dest_id.clone(),
-            resource_id.clone(),
+            generic_resource_id.clone(),
             metadata.clone()
         ));
         assert_events(vec![Event::bridge(RawEvent::GenericTransfer(
             dest_id.clone(),
             3,
-            resource_id,
+            generic_resource_id,
             metadata,
         ))]);
     })
@@ -197,17 +275,17 @@ fn asset_transfer_invalid_chain() {
         ))]);
 
         assert_noop!(
-            Bridge::transfer_fungible(bad_dest_id, resource_id.clone(), vec![], U256::zero()),
+            Bridge::transfer_fungible(&USER, bad_dest_id, resource_id.clone(), vec![], U256::zero()),
             Error::<Test>::ChainNotWhitelisted
         );
 
         assert_noop!(
-            Bridge::transfer_nonfungible(bad_dest_id, resource_id.clone(), vec![], vec![], vec![]),
+            Bridge::transfer_nonfungible(&USER, bad_dest_id, resource_id.clone(), vec![], vec![], vec![]),
             Error::<Test>::ChainNotWhitelisted
         );
 
         assert_noop!(
-            Bridge::transfer_generic(bad_dest_id, resource_id.clone(), vec![]),
+            Bridge::transfer_generic(&USER, bad_dest_id, resource_id.clone(), vec![]),
             Error::<Test>::ChainNotWhitelisted
         );
     })
@@ -252,25 +330,35 @@ fn make_proposal(r: Vec<u8>) -> mock::Call {
     Call::System(system::Call::remark(r))
 }
 
+// A generic resource for proposals made with `make_proposal`
+fn remark_resource() -> ResourceOf<Test> {
+    make_resource(ResourceKind::Generic, b"System.remark", &make_proposal(vec![]))
+}
+
+// Registers a resource of the given kind for outbound transfers
+fn register_outbound_resource(id: ResourceId, kind: ResourceKind) {
+    assert_ok!(Bridge::set_resource(
+        Origin::root(),
+        id,
+        make_resource(kind, b"Pallet.transfer", &make_proposal(vec![]))
+    ));
+}
+
+fn proposal_hash(proposal: &mock::Call) -> H256 {
+    BlakeTwo256::hash_of(proposal)
+}
+
 #[test]
 fn create_sucessful_proposal() {
     let src_id = 1;
-    let r_id = derive_resource_id(src_id, b"remark");
+    let r_id = derive_resource_id(src_id as u8, b"remark");
 
-    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+    new_test_ext_initialized(src_id, r_id, remark_resource()).execute_with(|| {
         let prop_id = 1;
         let proposal = make_proposal(vec![10]);
+        let prop_hash = proposal_hash(&proposal);
 
-        let pk: String = String::from("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d");
-        assert_ok!(Dorr::set_pk(Origin::signed(RELAYER_A), pk.from_hex().unwrap()));
-
-        run_to_block(11);
-
-        assert_ok!(Dorr::set_vrf_results(
-          Origin::signed(RELAYER_A),
-          String::from("dcd0f3a7d0af4a1336b7cad05ffedd3486ca88c6e32eb096b301dced2ae43f5a").from_hex().unwrap(),
-          String::from("fa44dbfe6f3d4b49b623777b28412fac2168a463360ef0b531fdb70a76643b07910a8a616f00861a6399d70477918d5cf04e18a7fe298779eae862003027f302").from_hex().unwrap())
-        );
+        activate_relayers();
 
         // Create proposal (& vote)
         assert_ok!(Bridge::acknowledge_proposal(
@@ -278,70 +366,54 @@ fn create_sucessful_proposal() {
             prop_id,
             src_id,
             r_id,
-            Box::new(proposal.clone())
+            ProposalOrHash::Proposal(Box::new(proposal.clone()))
         ));
-        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        let prop = Bridge::votes((src_id, prop_id), prop_hash).unwrap();
         let expected = ProposalVotes {
             votes_for: vec![RELAYER_A],
             votes_against: vec![],
             status: ProposalStatus::Initiated,
-            expiry: ProposalLifetime::get() + 11,
+            expiry: ProposalLifetime::get() + 21,
+            voters: Bridge::eligible_voters(),
+            resource_id: r_id,
         };
         assert_eq!(prop, expected);
 
-        let pk: String = String::from("8a3cac9282ba021cc6090f6ddfc826383300facb2101d6c736d794a5b25aa060");
-        assert_ok!(Dorr::set_pk(Origin::signed(RELAYER_B), pk.from_hex().unwrap()));
-
-        run_to_block(21);
-
-        assert_ok!(Dorr::set_vrf_results(
-          Origin::signed(RELAYER_B),
-          String::from("10a343aaa12503ee7e004a7c56eb6f3956cba77f38a62a6e9544daa7ab07a913").from_hex().unwrap(),
-          String::from("c8c4383bcf63585f6ae816e05779b13dc130af4c13c3869e5c59b4dffd0283046b3e62e7a09ed59a15246eeb7326046fcd48203d6a723375f5e9084fdba2a807").from_hex().unwrap())
-        );
-
         // Second relayer votes against
         assert_ok!(Bridge::reject_proposal(
             Origin::signed(RELAYER_B),
             prop_id,
             src_id,
             r_id,
-            Box::new(proposal.clone())
+            ProposalOrHash::Proposal(Box::new(proposal.clone()))
         ));
-        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        let prop = Bridge::votes((src_id, prop_id), prop_hash).unwrap();
         let expected = ProposalVotes {
             votes_for: vec![RELAYER_A],
             votes_against: vec![RELAYER_B],
             status: ProposalStatus::Initiated,
-            expiry: ProposalLifetime::get() + 11,
+            expiry: ProposalLifetime::get() + 21,
+            voters: Bridge::eligible_voters(),
+            resource_id: r_id,
         };
         assert_eq!(prop, expected);
 
-        let pk: String = String::from("e062f3b7ff6d5de1339ccb295d7202c440e9d8bf421ca30ea8c37eae0a7ef559");
-        assert_ok!(Dorr::set_pk(Origin::signed(RELAYER_C), pk.from_hex().unwrap()));
-
-        run_to_block(31);
-
-        assert_ok!(Dorr::set_vrf_results(
-          Origin::signed(RELAYER_C),
-          String::from("22ed779046c565be3c8f50c6e63cf7e6786a15de7283b86cac53528a2006516c").from_hex().unwrap(),
-          String::from("f0e53ab529aa808395b50842237b7e6ebae0a0694046470aa66336cd4b8b6700846706d0bf9bfbfb72e82f54423c94b0871f9272d1763169921d9c6faa1a350d").from_hex().unwrap())
-        );
-
         // Third relayer votes in favour
         assert_ok!(Bridge::acknowledge_proposal(
             Origin::signed(RELAYER_C),
             prop_id,
             src_id,
             r_id,
-            Box::new(proposal.clone())
+            ProposalOrHash::Proposal(Box::new(proposal.clone()))
         ));
-        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        let prop = Bridge::votes((src_id, prop_id), prop_hash).unwrap();
         let expected = ProposalVotes {
             votes_for: vec![RELAYER_A, RELAYER_C],
             votes_against: vec![RELAYER_B],
-            status: ProposalStatus::Approved,
-            expiry: ProposalLifetime::get() + 11,
+            status: ProposalStatus::Executed,
+            expiry: ProposalLifetime::get() + 21,
+            voters: Bridge::eligible_voters(),
+            resource_id: r_id,
         };
         assert_eq!(prop, expected);
 
@@ -358,22 +430,14 @@ fn create_sucessful_proposal() {
 #[test]
 fn create_unsucessful_proposal() {
     let src_id = 1;
-    let r_id = derive_resource_id(src_id, b"transfer");
+    let r_id = derive_resource_id(src_id as u8, b"transfer");
 
-    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+    new_test_ext_initialized(src_id, r_id, remark_resource()).execute_with(|| {
         let prop_id = 1;
         let proposal = make_proposal(vec![11]);
+        let prop_hash = proposal_hash(&proposal);
 
-        let pk: String = String::from("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d");
-        assert_ok!(Dorr::set_pk(Origin::signed(RELAYER_A), pk.from_hex().unwrap()));
-
-        run_to_block(11);
-
-        assert_ok!(Dorr::set_vrf_results(
-          Origin::signed(RELAYER_A),
-          String::from("dcd0f3a7d0af4a1336b7cad05ffedd3486ca88c6e32eb096b301dced2ae43f5a").from_hex().unwrap(),
-          String::from("fa44dbfe6f3d4b49b623777b28412fac2168a463360ef0b531fdb70a76643b07910a8a616f00861a6399d70477918d5cf04e18a7fe298779eae862003027f302").from_hex().unwrap())
-        );
+        activate_relayers();
 
         // Create proposal (& vote)
         assert_ok!(Bridge::acknowledge_proposal(
@@ -381,70 +445,54 @@ fn create_unsucessful_proposal() {
             prop_id,
             src_id,
             r_id,
-            Box::new(proposal.clone())
+            ProposalOrHash::Proposal(Box::new(proposal.clone()))
         ));
-        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        let prop = Bridge::votes((src_id, prop_id), prop_hash).unwrap();
         let expected = ProposalVotes {
             votes_for: vec![RELAYER_A],
             votes_against: vec![],
             status: ProposalStatus::Initiated,
-            expiry: ProposalLifetime::get() + 11,
+            expiry: ProposalLifetime::get() + 21,
+            voters: Bridge::eligible_voters(),
+            resource_id: r_id,
         };
         assert_eq!(prop, expected);
 
-        let pk: String = String::from("8a3cac9282ba021cc6090f6ddfc826383300facb2101d6c736d794a5b25aa060");
-        assert_ok!(Dorr::set_pk(Origin::signed(RELAYER_B), pk.from_hex().unwrap()));
-
-        run_to_block(21);
-
-        assert_ok!(Dorr::set_vrf_results(
-          Origin::signed(RELAYER_B),
-          String::from("10a343aaa12503ee7e004a7c56eb6f3956cba77f38a62a6e9544daa7ab07a913").from_hex().unwrap(),
-          String::from("c8c4383bcf63585f6ae816e05779b13dc130af4c13c3869e5c59b4dffd0283046b3e62e7a09ed59a15246eeb7326046fcd48203d6a723375f5e9084fdba2a807").from_hex().unwrap())
-        );
-
         // Second relayer votes against
         assert_ok!(Bridge::reject_proposal(
             Origin::signed(RELAYER_B),
             prop_id,
             src_id,
             r_id,
-            Box::new(proposal.clone())
+            ProposalOrHash::Proposal(Box::new(proposal.clone()))
         ));
-        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        let prop = Bridge::votes((src_id, prop_id), prop_hash).unwrap();
         let expected = ProposalVotes {
             votes_for: vec![RELAYER_A],
             votes_against: vec![RELAYER_B],
             status: ProposalStatus::Initiated,
-            expiry: ProposalLifetime::get() + 11,
+            expiry: ProposalLifetime::get() + 21,
+            voters: Bridge::eligible_voters(),
+            resource_id: r_id,
         };
         assert_eq!(prop, expected);
 
-        let pk: String = String::from("e062f3b7ff6d5de1339ccb295d7202c440e9d8bf421ca30ea8c37eae0a7ef559");
-        assert_ok!(Dorr::set_pk(Origin::signed(RELAYER_C), pk.from_hex().unwrap()));
-
-        run_to_block(31);
-
-        assert_ok!(Dorr::set_vrf_results(
-          Origin::signed(RELAYER_C),
-          String::from("22ed779046c565be3c8f50c6e63cf7e6786a15de7283b86cac53528a2006516c").from_hex().unwrap(),
-          String::from("f0e53ab529aa808395b50842237b7e6ebae0a0694046470aa66336cd4b8b6700846706d0bf9bfbfb72e82f54423c94b0871f9272d1763169921d9c6faa1a350d").from_hex().unwrap())
-        );
-
         // Third relayer votes against
         assert_ok!(Bridge::reject_proposal(
             Origin::signed(RELAYER_C),
             prop_id,
             src_id,
             r_id,
-            Box::new(proposal.clone())
+            ProposalOrHash::Proposal(Box::new(proposal.clone()))
         ));
-        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        let prop = Bridge::votes((src_id, prop_id), prop_hash).unwrap();
         let expected = ProposalVotes {
             votes_for: vec![RELAYER_A],
             votes_against: vec![RELAYER_B, RELAYER_C],
             status: ProposalStatus::Rejected,
-            expiry: ProposalLifetime::get() + 11,
+            expiry: ProposalLifetime::get() + 21,
+            voters: Bridge::eligible_voters(),
+            resource_id: r_id,
         };
         assert_eq!(prop, expected);
 
@@ -466,22 +514,14 @@ fn create_unsucessful_proposal() {
 #[test]
 fn execute_after_threshold_change() {
     let src_id = 1;
-    let r_id = derive_resource_id(src_id, b"transfer");
+    let r_id = derive_resource_id(src_id as u8, b"transfer");
 
-    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+    new_test_ext_initialized(src_id, r_id, remark_resource()).execute_with(|| {
         let prop_id = 1;
         let proposal = make_proposal(vec![11]);
+        let prop_hash = proposal_hash(&proposal);
 
-        let pk: String = String::from("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d");
-        assert_ok!(Dorr::set_pk(Origin::signed(RELAYER_A), pk.from_hex().unwrap()));
-
-        run_to_block(11);
-
-        assert_ok!(Dorr::set_vrf_results(
-          Origin::signed(RELAYER_A),
-          String::from("dcd0f3a7d0af4a1336b7cad05ffedd3486ca88c6e32eb096b301dced2ae43f5a").from_hex().unwrap(),
-          String::from("fa44dbfe6f3d4b49b623777b28412fac2168a463360ef0b531fdb70a76643b07910a8a616f00861a6399d70477918d5cf04e18a7fe298779eae862003027f302").from_hex().unwrap())
-        );
+        activate(&[RELAYER_A]);
 
         // Create proposal (& vote)
         assert_ok!(Bridge::acknowledge_proposal(
@@ -489,14 +529,16 @@ fn execute_after_threshold_change() {
             prop_id,
             src_id,
             r_id,
-            Box::new(proposal.clone())
+            ProposalOrHash::Proposal(Box::new(proposal.clone()))
         ));
-        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        let prop = Bridge::votes((src_id, prop_id), prop_hash).unwrap();
         let expected = ProposalVotes {
             votes_for: vec![RELAYER_A],
             votes_against: vec![],
             status: ProposalStatus::Initiated,
-            expiry: ProposalLifetime::get() + 11,
+            expiry: ProposalLifetime::get() + 21,
+            voters: vec![RELAYER_A],
+            resource_id: r_id,
         };
         assert_eq!(prop, expected);
 
@@ -508,15 +550,17 @@ fn execute_after_threshold_change() {
             Origin::signed(RELAYER_A),
             prop_id,
             src_id,
-            Box::new(proposal.clone())
+            ProposalOrHash::Proposal(Box::new(proposal.clone()))
         ));
 
-        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        let prop = Bridge::votes((src_id, prop_id), prop_hash).unwrap();
         let expected = ProposalVotes {
             votes_for: vec![RELAYER_A],
             votes_against: vec![],
-            status: ProposalStatus::Approved,
-            expiry: ProposalLifetime::get() + 11,
+            status: ProposalStatus::Executed,
+            expiry: ProposalLifetime::get() + 21,
+            voters: vec![RELAYER_A],
+            resource_id: r_id,
         };
         assert_eq!(prop, expected);
 
@@ -538,22 +582,14 @@ fn execute_after_threshold_change() {
 #[test]
 fn proposal_expires() {
     let src_id = 1;
-    let r_id = derive_resource_id(src_id, b"remark");
+    let r_id = derive_resource_id(src_id as u8, b"remark");
 
-    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+    new_test_ext_initialized(src_id, r_id, remark_resource()).execute_with(|| {
         let prop_id = 1;
         let proposal = make_proposal(vec![10]);
+        let prop_hash = proposal_hash(&proposal);
 
-        let pk: String = String::from("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d");
-        assert_ok!(Dorr::set_pk(Origin::signed(RELAYER_A), pk.from_hex().unwrap()));
-
-        run_to_block(11);
-
-        assert_ok!(Dorr::set_vrf_results(
-          Origin::signed(RELAYER_A),
-          String::from("dcd0f3a7d0af4a1336b7cad05ffedd3486ca88c6e32eb096b301dced2ae43f5a").from_hex().unwrap(),
-          String::from("fa44dbfe6f3d4b49b623777b28412fac2168a463360ef0b531fdb70a76643b07910a8a616f00861a6399d70477918d5cf04e18a7fe298779eae862003027f302").from_hex().unwrap())
-        );
+        activate(&[RELAYER_A]);
 
         // Create proposal (& vote)
         assert_ok!(Bridge::acknowledge_proposal(
@@ -561,28 +597,21 @@ fn proposal_expires() {
             prop_id,
             src_id,
             r_id,
-            Box::new(proposal.clone())
+            ProposalOrHash::Proposal(Box::new(proposal.clone()))
         ));
-        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        let prop = Bridge::votes((src_id, prop_id), prop_hash).unwrap();
         let expected = ProposalVotes {
             votes_for: vec![RELAYER_A],
             votes_against: vec![],
             status: ProposalStatus::Initiated,
-            expiry: ProposalLifetime::get() + 11,
+            expiry: ProposalLifetime::get() + 21,
+            voters: vec![RELAYER_A],
+            resource_id: r_id,
         };
         assert_eq!(prop, expected);
 
-        let pk: String = String::from("8a3cac9282ba021cc6090f6ddfc826383300facb2101d6c736d794a5b25aa060");
-        assert_ok!(Dorr::set_pk(Origin::signed(RELAYER_B), pk.from_hex().unwrap()));
-
         // Increment enough blocks such that now == expiry
-        run_to_block(61);
-
-        assert_ok!(Dorr::set_vrf_results(
-          Origin::signed(RELAYER_B),
-          String::from("10a343aaa12503ee7e004a7c56eb6f3956cba77f38a62a6e9544daa7ab07a913").from_hex().unwrap(),
-          String::from("c8c4383bcf63585f6ae816e05779b13dc130af4c13c3869e5c59b4dffd0283046b3e62e7a09ed59a15246eeb7326046fcd48203d6a723375f5e9084fdba2a807").from_hex().unwrap())
-        );
+        run_to_block(ProposalLifetime::get() + 21);
 
         // Attempt to submit a vote should fail
         assert_noop!(
@@ -591,18 +620,20 @@ fn proposal_expires() {
                 prop_id,
                 src_id,
                 r_id,
-                Box::new(proposal.clone())
+                ProposalOrHash::Proposal(Box::new(proposal.clone()))
             ),
             Error::<Test>::ProposalExpired
         );
 
         // Proposal state should remain unchanged
-        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        let prop = Bridge::votes((src_id, prop_id), prop_hash).unwrap();
         let expected = ProposalVotes {
             votes_for: vec![RELAYER_A],
             votes_against: vec![],
             status: ProposalStatus::Initiated,
-            expiry: ProposalLifetime::get() + 11,
+            expiry: ProposalLifetime::get() + 21,
+            voters: vec![RELAYER_A],
+            resource_id: r_id,
         };
         assert_eq!(prop, expected);
 
@@ -612,21 +643,2259 @@ fn proposal_expires() {
                 Origin::signed(RELAYER_C),
                 prop_id,
                 src_id,
-                Box::new(proposal.clone())
+                ProposalOrHash::Proposal(Box::new(proposal.clone()))
             ),
             Error::<Test>::ProposalExpired
         );
-        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        let prop = Bridge::votes((src_id, prop_id), prop_hash).unwrap();
         let expected = ProposalVotes {
             votes_for: vec![RELAYER_A],
             votes_against: vec![],
             status: ProposalStatus::Initiated,
-            expiry: ProposalLifetime::get() + 11,
+            expiry: ProposalLifetime::get() + 21,
+            voters: vec![RELAYER_A],
+            resource_id: r_id,
         };
         assert_eq!(prop, expected);
 
-        assert_events(vec![Event::bridge(RawEvent::VoteFor(
-            src_id, prop_id, RELAYER_A,
-        ))]);
+        // A wasn't elected again after epoch 3
+        assert_events(vec![
+            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_A)),
+            Event::dorr(dorr::RawEvent::ActiveSetChanged(4, vec![])),
+        ]);
+    })
+}
+
+#[test]
+fn proposal_failure_is_recorded() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id as u8, b"heap_pages");
+
+    let resource = make_resource(
+        ResourceKind::Generic,
+        b"System.set_heap_pages",
+        &Call::System(system::Call::set_heap_pages(1)),
+    );
+
+    new_test_ext_initialized(src_id, r_id, resource).execute_with(|| {
+        let prop_id = 1;
+        // Requires a root origin, so dispatching it from the bridge account fails
+        let proposal = Call::System(system::Call::set_heap_pages(1));
+        let prop_hash = proposal_hash(&proposal);
+
+        activate_relayers();
+
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            ProposalOrHash::Proposal(Box::new(proposal.clone()))
+        ));
+        // The vote reaching the threshold is kept even though the call fails
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_B),
+            prop_id,
+            src_id,
+            r_id,
+            ProposalOrHash::Proposal(Box::new(proposal.clone()))
+        ));
+
+        let prop = Bridge::votes((src_id, prop_id), prop_hash).unwrap();
+        let expected = ProposalVotes {
+            votes_for: vec![RELAYER_A, RELAYER_B],
+            votes_against: vec![],
+            status: ProposalStatus::Failed(DispatchError::BadOrigin),
+            expiry: ProposalLifetime::get() + 21,
+            voters: Bridge::eligible_voters(),
+            resource_id: r_id,
+        };
+        assert_eq!(prop, expected);
+
+        // A failed proposal is complete and can't be voted on again
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_C),
+                prop_id,
+                src_id,
+                r_id,
+                ProposalOrHash::Proposal(Box::new(proposal.clone()))
+            ),
+            Error::<Test>::ProposalAlreadyComplete
+        );
+
+        assert_events(vec![
+            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_A)),
+            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_B)),
+            Event::bridge(RawEvent::ProposalApproved(src_id, prop_id)),
+            Event::bridge(RawEvent::ProposalFailed(src_id, prop_id, DispatchError::BadOrigin)),
+        ]);
+
+        // The failed call still consumes the nonce, so the deposit can't be executed again
+        // through another proposal
+        assert!(Bridge::nonce_executed(src_id, prop_id));
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_C),
+                prop_id,
+                src_id,
+                r_id,
+                ProposalOrHash::Proposal(Box::new(Call::System(system::Call::set_heap_pages(2))))
+            ),
+            Error::<Test>::NonceAlreadyExecuted
+        );
+    })
+}
+
+#[test]
+fn vote_by_proposal_hash() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id as u8, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, remark_resource()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+        let prop_hash = proposal_hash(&proposal);
+
+        activate_relayers();
+
+        // A proposal can't be created from its hash alone
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_A),
+                prop_id,
+                src_id,
+                r_id,
+                ProposalOrHash::Hash(prop_hash)
+            ),
+            Error::<Test>::ProposalPreimageMissing
+        );
+
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            ProposalOrHash::Proposal(Box::new(proposal.clone()))
+        ));
+        assert_eq!(Bridge::proposal_preimage((src_id, prop_id), prop_hash), Some(proposal.clone()));
+
+        // Once the call is known, later votes only need its hash
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_B),
+            prop_id,
+            src_id,
+            r_id,
+            ProposalOrHash::Hash(prop_hash)
+        ));
+
+        let prop = Bridge::votes((src_id, prop_id), prop_hash).unwrap();
+        assert_eq!(prop.votes_for, vec![RELAYER_A, RELAYER_B]);
+        assert_eq!(prop.status, ProposalStatus::Executed);
+
+        assert_events(vec![
+            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_A)),
+            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_B)),
+            Event::bridge(RawEvent::ProposalApproved(src_id, prop_id)),
+            Event::bridge(RawEvent::ProposalSucceeded(src_id, prop_id)),
+        ]);
+    })
+}
+
+#[test]
+fn votes_carry_the_call_without_preimage_store() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id as u8, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, remark_resource()).execute_with(|| {
+        StorePreimages::set(false);
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+        let prop_hash = proposal_hash(&proposal);
+
+        activate_relayers();
+
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            ProposalOrHash::Proposal(Box::new(proposal.clone()))
+        ));
+        assert_eq!(Bridge::proposal_preimage((src_id, prop_id), prop_hash), None);
+
+        // The call isn't known, so votes by hash are rejected
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_B),
+                prop_id,
+                src_id,
+                r_id,
+                ProposalOrHash::Hash(prop_hash)
+            ),
+            Error::<Test>::ProposalPreimageMissing
+        );
+
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_B),
+            prop_id,
+            src_id,
+            r_id,
+            ProposalOrHash::Proposal(Box::new(proposal.clone()))
+        ));
+        let prop = Bridge::votes((src_id, prop_id), prop_hash).unwrap();
+        assert_eq!(prop.status, ProposalStatus::Executed);
+    })
+}
+
+#[test]
+fn executed_nonce_cannot_be_replayed() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id as u8, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, remark_resource()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+        let prop_hash = proposal_hash(&proposal);
+        let other = make_proposal(vec![11]);
+
+        activate_relayers();
+
+        // Two competing proposals for the same nonce
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            ProposalOrHash::Proposal(Box::new(proposal.clone()))
+        ));
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            ProposalOrHash::Proposal(Box::new(other.clone()))
+        ));
+
+        // The first one is executed
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_B),
+            prop_id,
+            src_id,
+            r_id,
+            ProposalOrHash::Proposal(Box::new(proposal.clone()))
+        ));
+        assert_eq!(Bridge::votes((src_id, prop_id), prop_hash).unwrap().status, ProposalStatus::Executed);
+        assert!(Bridge::nonce_executed(src_id, prop_id));
+
+        // The pending one can no longer be voted on
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_B),
+                prop_id,
+                src_id,
+                r_id,
+                ProposalOrHash::Proposal(Box::new(other.clone()))
+            ),
+            Error::<Test>::NonceAlreadyExecuted
+        );
+
+        // And no new proposal can be created for the nonce, whatever call it carries
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_C),
+                prop_id,
+                src_id,
+                r_id,
+                ProposalOrHash::Proposal(Box::new(make_proposal(vec![12])))
+            ),
+            Error::<Test>::NonceAlreadyExecuted
+        );
+    })
+}
+
+#[test]
+fn executed_nonce_watermark() {
+    new_test_ext().execute_with(|| {
+        let src_id = 1;
+
+        Bridge::mark_nonce_executed(src_id, 1);
+        assert_eq!(Bridge::executed_nonce_watermark(src_id), 1);
+
+        // Out of order nonces are tracked individually
+        Bridge::mark_nonce_executed(src_id, 3);
+        Bridge::mark_nonce_executed(src_id, 4);
+        assert_eq!(Bridge::executed_nonce_watermark(src_id), 1);
+        assert!(!Bridge::nonce_executed(src_id, 2));
+        assert!(Bridge::nonce_executed(src_id, 3));
+        assert!(Bridge::nonce_executed(src_id, 4));
+        assert!(!Bridge::nonce_executed(src_id, 5));
+
+        // Filling the gap advances the watermark over them
+        Bridge::mark_nonce_executed(src_id, 2);
+        assert_eq!(Bridge::executed_nonce_watermark(src_id), 4);
+        assert!(!Bridge::executed_nonces(src_id, 3));
+        assert!(!Bridge::executed_nonces(src_id, 4));
+
+        // Other chains are unaffected
+        assert!(!Bridge::nonce_executed(2, 1));
+    })
+}
+
+#[test]
+fn pause_and_unpause() {
+    new_test_ext().execute_with(|| {
+        let scope = PauseScope::Chain(2);
+
+        assert_noop!(
+            Bridge::pause(Origin::signed(RELAYER_A), PauseScope::Bridge),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Bridge::unpause(Origin::root(), scope.clone()),
+            Error::<Test>::NotPaused
+        );
+
+        assert_ok!(Bridge::pause(Origin::root(), scope.clone()));
+        assert!(Bridge::paused(scope.clone()));
+        assert_noop!(
+            Bridge::pause(Origin::root(), scope.clone()),
+            Error::<Test>::AlreadyPaused
+        );
+        assert_ok!(Bridge::pause(Origin::root(), PauseScope::Bridge));
+        assert_eq!(Bridge::paused_scopes().len(), 2);
+
+        assert_ok!(Bridge::unpause(Origin::root(), scope.clone()));
+        assert!(!Bridge::paused(scope.clone()));
+        assert_eq!(Bridge::paused_scopes(), vec![PauseScope::Bridge]);
+
+        assert_events(vec![
+            Event::bridge(RawEvent::Paused(scope.clone())),
+            Event::bridge(RawEvent::Paused(PauseScope::Bridge)),
+            Event::bridge(RawEvent::Unpaused(scope)),
+        ]);
+    })
+}
+
+#[test]
+fn paused_outbound_transfers() {
+    new_test_ext().execute_with(|| {
+        let dest_id = 2;
+        let resource_id = [1; 32];
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_id));
+        register_outbound_resource(resource_id, ResourceKind::Generic);
+
+        let scopes: Vec<(PauseScope, DispatchError)> = vec![
+            (PauseScope::Bridge, Error::<Test>::BridgePaused.into()),
+            (PauseScope::Direction(BridgeDirection::Outbound), Error::<Test>::DirectionPaused.into()),
+            (PauseScope::Chain(dest_id), Error::<Test>::ChainPaused.into()),
+            (PauseScope::Resource(resource_id), Error::<Test>::ResourcePaused.into()),
+        ];
+        for (scope, error) in scopes {
+            assert_ok!(Bridge::pause(Origin::root(), scope.clone()));
+            assert_noop!(
+                Bridge::transfer_fungible(&USER, dest_id, resource_id, vec![], U256::one()),
+                error
+            );
+            assert_noop!(
+                Bridge::transfer_nonfungible(&USER, dest_id, resource_id, vec![], vec![], vec![]),
+                error
+            );
+            assert_noop!(
+                Bridge::transfer_generic(&USER, dest_id, resource_id, vec![]),
+                error
+            );
+            assert_ok!(Bridge::unpause(Origin::root(), scope));
+        }
+
+        // Pausing the other direction doesn't affect outbound transfers
+        assert_ok!(Bridge::pause(Origin::root(), PauseScope::Direction(BridgeDirection::Inbound)));
+        assert_ok!(Bridge::transfer_generic(&USER, dest_id, resource_id, vec![]));
+    })
+}
+
+#[test]
+fn paused_inbound_proposals() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id as u8, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, remark_resource()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+        let prop_hash = proposal_hash(&proposal);
+
+        activate_relayers();
+
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            ProposalOrHash::Proposal(Box::new(proposal.clone()))
+        ));
+
+        assert_ok!(Bridge::pause(Origin::root(), PauseScope::Direction(BridgeDirection::Inbound)));
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_B),
+                prop_id,
+                src_id,
+                r_id,
+                ProposalOrHash::Hash(prop_hash)
+            ),
+            Error::<Test>::DirectionPaused
+        );
+        assert_noop!(
+            Bridge::reject_proposal(
+                Origin::signed(RELAYER_B),
+                prop_id,
+                src_id,
+                r_id,
+                ProposalOrHash::Hash(prop_hash)
+            ),
+            Error::<Test>::DirectionPaused
+        );
+        assert_noop!(
+            Bridge::eval_vote_state(
+                Origin::signed(RELAYER_B),
+                prop_id,
+                src_id,
+                ProposalOrHash::Hash(prop_hash)
+            ),
+            Error::<Test>::DirectionPaused
+        );
+
+        assert_ok!(Bridge::unpause(Origin::root(), PauseScope::Direction(BridgeDirection::Inbound)));
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_B),
+            prop_id,
+            src_id,
+            r_id,
+            ProposalOrHash::Hash(prop_hash)
+        ));
+        assert_eq!(Bridge::votes((src_id, prop_id), prop_hash).unwrap().status, ProposalStatus::Executed);
+    })
+}
+
+#[test]
+fn runtime_api_queries() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id as u8, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, remark_resource()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+        let prop_hash = proposal_hash(&proposal);
+
+        assert_eq!(Bridge::whitelisted_chains(), vec![(src_id, 0)]);
+        assert_eq!(Bridge::registered_resources(), vec![(r_id, remark_resource())]);
+
+        let mut relayers = Bridge::relayer_set();
+        relayers.sort();
+        assert_eq!(relayers, vec![RELAYER_A, RELAYER_B, RELAYER_C]);
+
+        assert_ok!(Bridge::remove_relayer(Origin::root(), RELAYER_C));
+        let mut relayers = Bridge::relayer_set();
+        relayers.sort();
+        assert_eq!(relayers, vec![RELAYER_A, RELAYER_B]);
+
+        assert_eq!(Bridge::proposals(src_id, prop_id, None, 10), vec![]);
+
+        activate(&[RELAYER_A, RELAYER_B]);
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            ProposalOrHash::Proposal(Box::new(proposal.clone()))
+        ));
+
+        let expected = ProposalVotes {
+            votes_for: vec![RELAYER_A],
+            votes_against: vec![],
+            status: ProposalStatus::Initiated,
+            expiry: System::block_number() + ProposalLifetime::get(),
+            voters: Bridge::eligible_voters(),
+            resource_id: r_id,
+        };
+        assert_eq!(Bridge::proposals(src_id, prop_id, None, 10), vec![(prop_hash, expected)]);
+        assert_eq!(Bridge::proposals(src_id, prop_id + 1, None, 10), vec![]);
+
+        // Proposals of a deposit are returned in pages
+        let other_proposal = make_proposal(vec![11]);
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_B),
+            prop_id,
+            src_id,
+            r_id,
+            ProposalOrHash::Proposal(Box::new(other_proposal.clone()))
+        ));
+        let all = Bridge::proposals(src_id, prop_id, None, 10);
+        assert_eq!(all.len(), 2);
+        let first = Bridge::proposals(src_id, prop_id, None, 1);
+        assert_eq!(first, all[..1].to_vec());
+        let second = Bridge::proposals(src_id, prop_id, Some(first[0].0), 1);
+        assert_eq!(second, all[1..].to_vec());
+        assert_eq!(Bridge::proposals(src_id, prop_id, Some(second[0].0), 1), vec![]);
+        assert_eq!(Bridge::proposals(src_id, prop_id, None, 0), vec![]);
+    })
+}
+
+#[test]
+fn outbound_transfer_fee() {
+    let dest_id = 2;
+    let resource_id = [1; 32];
+    let fee = 10;
+
+    new_test_ext().execute_with(|| {
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_id));
+        register_outbound_resource(resource_id, ResourceKind::Generic);
+        assert_ok!(Bridge::set_fee(Origin::root(), dest_id, fee));
+        assert_eq!(Bridge::bridge_fee(dest_id), fee);
+        assert_noop!(Bridge::set_fee(Origin::signed(USER), dest_id, fee), DispatchError::BadOrigin);
+
+        // The source must be able to pay the fee
+        assert_noop!(
+            Bridge::transfer_generic(&USER, dest_id, resource_id, vec![]),
+            pallet_balances::Error::<Test, _>::InsufficientBalance
+        );
+
+        Balances::make_free_balance_be(&USER, 100);
+        assert_ok!(Bridge::transfer_generic(&USER, dest_id, resource_id, vec![]));
+        assert_eq!(Balances::free_balance(USER), 100 - fee);
+        assert_eq!(Balances::free_balance(Bridge::reward_pot_id()), fee);
+        assert_events(vec![
+            Event::balances(pallet_balances::RawEvent::Transfer(USER, Bridge::reward_pot_id(), fee)),
+            Event::bridge(RawEvent::GenericTransfer(dest_id, 1, resource_id, vec![])),
+        ]);
+
+        // A zero fee disables charging
+        assert_ok!(Bridge::set_fee(Origin::root(), dest_id, 0));
+        assert_ok!(Bridge::transfer_generic(&USER, dest_id, resource_id, vec![]));
+        assert_eq!(Balances::free_balance(USER), 100 - fee);
+
+        assert_events(vec![
+            Event::bridge(RawEvent::FeeChanged(dest_id, 0)),
+            Event::bridge(RawEvent::GenericTransfer(dest_id, 2, resource_id, vec![])),
+        ]);
+    })
+}
+
+#[test]
+fn relayers_rewarded_with_outcome() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id as u8, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, remark_resource()).execute_with(|| {
+        let proposal = make_proposal(vec![10]);
+
+        // Existential deposit plus two and a half proposal rewards
+        Balances::make_free_balance_be(&Bridge::reward_pot_id(), 26);
+        assert_eq!(Bridge::unallocated_rewards(), 25);
+
+        activate_relayers();
+
+        // Approved, the relayers who voted for it share the reward
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            1,
+            src_id,
+            r_id,
+            ProposalOrHash::Proposal(Box::new(proposal.clone()))
+        ));
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_B),
+            1,
+            src_id,
+            r_id,
+            ProposalOrHash::Proposal(Box::new(proposal.clone()))
+        ));
+        assert_eq!(Bridge::pending_rewards(RELAYER_A), 5);
+        assert_eq!(Bridge::pending_rewards(RELAYER_B), 5);
+
+        // Rejected, the relayers who voted against it share the reward
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            2,
+            src_id,
+            r_id,
+            ProposalOrHash::Proposal(Box::new(proposal.clone()))
+        ));
+        assert_ok!(Bridge::reject_proposal(
+            Origin::signed(RELAYER_B),
+            2,
+            src_id,
+            r_id,
+            ProposalOrHash::Proposal(Box::new(proposal.clone()))
+        ));
+        assert_ok!(Bridge::reject_proposal(
+            Origin::signed(RELAYER_C),
+            2,
+            src_id,
+            r_id,
+            ProposalOrHash::Proposal(Box::new(proposal.clone()))
+        ));
+        assert_eq!(Bridge::pending_rewards(RELAYER_A), 5);
+        assert_eq!(Bridge::pending_rewards(RELAYER_B), 10);
+        assert_eq!(Bridge::pending_rewards(RELAYER_C), 5);
+        assert_eq!(Bridge::total_pending_rewards(), 20);
+
+        // Only what is left in the pot is paid out
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            3,
+            src_id,
+            r_id,
+            ProposalOrHash::Proposal(Box::new(proposal.clone()))
+        ));
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_C),
+            3,
+            src_id,
+            r_id,
+            ProposalOrHash::Proposal(Box::new(proposal.clone()))
+        ));
+        assert_eq!(Bridge::pending_rewards(RELAYER_A), 7);
+        assert_eq!(Bridge::pending_rewards(RELAYER_C), 7);
+        assert_eq!(Bridge::unallocated_rewards(), 1);
+
+        assert_events(vec![
+            Event::bridge(RawEvent::VoteFor(src_id, 3, RELAYER_C)),
+            Event::bridge(RawEvent::ProposalApproved(src_id, 3)),
+            Event::bridge(RawEvent::ProposalSucceeded(src_id, 3)),
+            Event::bridge(RawEvent::RelayersRewarded(src_id, 3, 2)),
+        ]);
+
+        assert_ok!(Bridge::claim_rewards(Origin::signed(RELAYER_B)));
+        assert_eq!(Balances::free_balance(RELAYER_B), 10);
+        assert_eq!(Bridge::pending_rewards(RELAYER_B), 0);
+        assert_eq!(Bridge::total_pending_rewards(), 14);
+        assert_eq!(Balances::free_balance(Bridge::reward_pot_id()), 16);
+        assert_events(vec![Event::bridge(RawEvent::RewardsClaimed(RELAYER_B, 10))]);
+
+        assert_noop!(Bridge::claim_rewards(Origin::signed(RELAYER_B)), Error::<Test>::NoRewards);
+    })
+}
+
+#[test]
+fn claim_rewards_is_clamped_to_the_pot() {
+    new_test_ext().execute_with(|| {
+        ExistentialDeposit::set(5);
+        let pot = Bridge::reward_pot_id();
+        <PendingRewards<Test>>::insert(RELAYER_A, 10);
+        <PendingRewards<Test>>::insert(RELAYER_B, 3);
+        <TotalPendingRewards<Test>>::put(13);
+
+        // Nothing above the existential deposit of the pot
+        Balances::make_free_balance_be(&pot, 5);
+        assert_noop!(
+            Bridge::claim_rewards(Origin::signed(RELAYER_A)),
+            Error::<Test>::RewardPotExhausted
+        );
+
+        // Only the free balance above the existential deposit is paid, the rest stays pending
+        Balances::make_free_balance_be(&pot, 12);
+        assert_ok!(Bridge::claim_rewards(Origin::signed(RELAYER_A)));
+        assert_eq!(Balances::free_balance(RELAYER_A), 7);
+        assert_eq!(Balances::free_balance(&pot), 5);
+        assert_eq!(Bridge::pending_rewards(RELAYER_A), 3);
+        assert_eq!(Bridge::total_pending_rewards(), 6);
+        assert_events(vec![Event::bridge(RawEvent::RewardsClaimed(RELAYER_A, 7))]);
+
+        // A payout below the existential deposit can't create an account
+        Balances::make_free_balance_be(&pot, 20);
+        assert_noop!(
+            Bridge::claim_rewards(Origin::signed(RELAYER_B)),
+            Error::<Test>::RewardBelowExistentialDeposit
+        );
+
+        // But is paid to an existing one
+        assert_ok!(Bridge::claim_rewards(Origin::signed(RELAYER_A)));
+        assert_eq!(Balances::free_balance(RELAYER_A), 10);
+        assert_eq!(Bridge::pending_rewards(RELAYER_A), 0);
+        assert_eq!(Bridge::total_pending_rewards(), 3);
+        assert_eq!(Balances::free_balance(&pot), 17);
+    })
+}
+
+#[test]
+fn bonding_required_to_join() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(Bridge::bond(Origin::signed(RELAYER_A)), Error::<Test>::BondingDisabled);
+
+        RelayerBond::set(100);
+        assert_noop!(
+            Bridge::add_relayer(Origin::root(), RELAYER_A),
+            Error::<Test>::InsufficientBond
+        );
+        assert_noop!(
+            Bridge::bond(Origin::signed(RELAYER_A)),
+            pallet_balances::Error::<Test, _>::InsufficientBalance
+        );
+
+        Balances::make_free_balance_be(&RELAYER_A, 150);
+        assert_ok!(Bridge::bond(Origin::signed(RELAYER_A)));
+        assert_eq!(Balances::reserved_balance(RELAYER_A), 100);
+        assert_noop!(Bridge::bond(Origin::signed(RELAYER_A)), Error::<Test>::AlreadyBonded);
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_A));
+
+        // Unbonding leaves the set, the bond stays reserved for the delay
+        assert_ok!(Bridge::unbond(Origin::signed(RELAYER_A)));
+        assert!(!Bridge::is_relayer(&RELAYER_A));
+        assert_eq!(Bridge::relayer_count(), 0);
+        assert_noop!(Bridge::unbond(Origin::signed(RELAYER_A)), Error::<Test>::AlreadyUnbonding);
+        assert_noop!(Bridge::bond(Origin::signed(RELAYER_A)), Error::<Test>::AlreadyUnbonding);
+        assert_noop!(
+            Bridge::add_relayer(Origin::root(), RELAYER_A),
+            Error::<Test>::InsufficientBond
+        );
+        assert_noop!(
+            Bridge::withdraw_unbonded(Origin::signed(RELAYER_A)),
+            Error::<Test>::BondStillLocked
+        );
+
+        run_to_block(1 + UnbondingDelay::get());
+        assert_ok!(Bridge::withdraw_unbonded(Origin::signed(RELAYER_A)));
+        assert_eq!(Balances::reserved_balance(RELAYER_A), 0);
+        assert_eq!(Balances::free_balance(RELAYER_A), 150);
+        assert_noop!(
+            Bridge::withdraw_unbonded(Origin::signed(RELAYER_A)),
+            Error::<Test>::NotBonded
+        );
+
+        assert_events(vec![
+            Event::bridge(RawEvent::Bonded(RELAYER_A, 100)),
+            Event::bridge(RawEvent::RelayerAdded(RELAYER_A)),
+            Event::bridge(RawEvent::RelayerRemoved(RELAYER_A)),
+            Event::bridge(RawEvent::Unbonding(RELAYER_A, 1 + UnbondingDelay::get())),
+            Event::balances(pallet_balances::RawEvent::Unreserved(RELAYER_A, 100)),
+            Event::bridge(RawEvent::Withdrawn(RELAYER_A, 100)),
+        ]);
+    })
+}
+
+#[test]
+fn unbonding_ignores_admin_delay() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id as u8, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, remark_resource()).execute_with(|| {
+        let prop = ProposalOrHash::Proposal(Box::new(make_proposal(vec![10])));
+        RelayerBond::set(100);
+        Balances::make_free_balance_be(&RELAYER_A, 100);
+        assert_ok!(Bridge::bond(Origin::signed(RELAYER_A)));
+        activate_relayers();
+        AdminDelay::set(5);
+        let now = System::block_number();
+
+        // The relayer leaves right away, nothing is scheduled that the admin could veto
+        assert_ok!(Bridge::unbond(Origin::signed(RELAYER_A)));
+        assert!(!Bridge::is_relayer(&RELAYER_A));
+        assert_eq!(Bridge::next_admin_action_id(), 0);
+        assert_noop!(
+            Bridge::acknowledge_proposal(Origin::signed(RELAYER_A), 1, src_id, r_id, prop),
+            Error::<Test>::MustBeRelayer
+        );
+        assert_events(vec![
+            Event::bridge(RawEvent::RelayerRemoved(RELAYER_A)),
+            Event::bridge(RawEvent::Unbonding(RELAYER_A, now + UnbondingDelay::get())),
+        ]);
+
+        // The bond is released on the unbonding schedule
+        run_to_block_with_pruning(now + UnbondingDelay::get());
+        assert_ok!(Bridge::withdraw_unbonded(Origin::signed(RELAYER_A)));
+        assert_eq!(Balances::free_balance(RELAYER_A), 100);
+    })
+}
+
+#[test]
+fn relayers_slashed_against_outcome() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id as u8, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, remark_resource()).execute_with(|| {
+        let proposal = make_proposal(vec![10]);
+        // Requires a root origin, so dispatching it from the bridge account fails
+        let failing_proposal = Call::System(system::Call::set_heap_pages(1));
+        let failing_r_id = derive_resource_id(src_id as u8, b"heap_pages");
+        let failing_resource =
+            make_resource(ResourceKind::Generic, b"System.set_heap_pages", &failing_proposal);
+        assert_ok!(Bridge::set_resource(Origin::root(), failing_r_id, failing_resource));
+
+        RelayerBond::set(100);
+        for relayer in [RELAYER_A, RELAYER_B, RELAYER_C].iter() {
+            Balances::make_free_balance_be(relayer, 100);
+            assert_ok!(Bridge::bond(Origin::signed(*relayer)));
+        }
+        activate_relayers();
+
+        let vote = |relayer, nonce, r_id, call: &Call, in_favour| {
+            let call = ProposalOrHash::Proposal(Box::new(call.clone()));
+            if in_favour {
+                Bridge::acknowledge_proposal(Origin::signed(relayer), nonce, src_id, r_id, call)
+            } else {
+                Bridge::reject_proposal(Origin::signed(relayer), nonce, src_id, r_id, call)
+            }
+        };
+
+        // Executed, the relayer who voted against it is slashed
+        assert_ok!(vote(RELAYER_A, 1, r_id, &proposal, true));
+        assert_ok!(vote(RELAYER_C, 1, r_id, &proposal, false));
+        assert_ok!(vote(RELAYER_B, 1, r_id, &proposal, true));
+        assert_events(vec![
+            Event::bridge(RawEvent::ProposalApproved(src_id, 1)),
+            Event::bridge(RawEvent::ProposalSucceeded(src_id, 1)),
+            Event::bridge(RawEvent::RelayerSlashed(RELAYER_C, 10)),
+        ]);
+
+        // Failed execution, the relayers who voted for it are slashed instead of rewarded
+        Balances::make_free_balance_be(&Bridge::reward_pot_id(), 11);
+        assert_ok!(vote(RELAYER_A, 2, failing_r_id, &failing_proposal, true));
+        assert_ok!(vote(RELAYER_B, 2, failing_r_id, &failing_proposal, true));
+        assert_events(vec![
+            Event::bridge(RawEvent::ProposalApproved(src_id, 2)),
+            Event::bridge(RawEvent::ProposalFailed(src_id, 2, DispatchError::BadOrigin)),
+            Event::bridge(RawEvent::RelayerSlashed(RELAYER_A, 10)),
+            Event::bridge(RawEvent::RelayerSlashed(RELAYER_B, 10)),
+        ]);
+        assert_eq!(Bridge::pending_rewards(RELAYER_A), 0);
+        assert_eq!(Bridge::pending_rewards(RELAYER_B), 0);
+        assert_eq!(Bridge::total_pending_rewards(), 0);
+
+        // Slashed relayers can't vote until they top up their bond
+        assert_noop!(
+            vote(RELAYER_A, 3, r_id, &proposal, true),
+            Error::<Test>::InsufficientBond
+        );
+        for relayer in [RELAYER_A, RELAYER_B, RELAYER_C].iter() {
+            Balances::make_free_balance_be(relayer, 10);
+            assert_ok!(Bridge::bond(Origin::signed(*relayer)));
+            assert_eq!(Balances::reserved_balance(relayer), 100);
+        }
+        assert_events(vec![Event::bridge(RawEvent::Bonded(RELAYER_C, 10))]);
+
+        // Rejected, the relayer who voted for it is slashed
+        assert_ok!(vote(RELAYER_A, 3, r_id, &proposal, true));
+        assert_ok!(vote(RELAYER_B, 3, r_id, &proposal, false));
+        assert_ok!(vote(RELAYER_C, 3, r_id, &proposal, false));
+        assert_events(vec![
+            Event::bridge(RawEvent::VoteAgainst(src_id, 3, RELAYER_C)),
+            Event::bridge(RawEvent::RelayersRewarded(src_id, 3, 5)),
+            Event::bridge(RawEvent::RelayerSlashed(RELAYER_A, 10)),
+            Event::bridge(RawEvent::ProposalRejected(src_id, 3)),
+        ]);
+
+        assert_eq!(Bridge::bonds(RELAYER_A).unwrap().amount, 90);
+        assert_eq!(Balances::reserved_balance(RELAYER_A), 90);
+        assert_eq!(Bridge::bonds(RELAYER_B).unwrap().amount, 100);
+        assert_eq!(Bridge::bonds(RELAYER_C).unwrap().amount, 100);
+    })
+}
+
+#[test]
+fn admin_changes_are_delayed() {
+    new_test_ext().execute_with(|| {
+        AdminDelay::set(5);
+        let veto = Origin::signed(1);
+
+        assert_noop!(Bridge::set_threshold(Origin::root(), 0), Error::<Test>::InvalidThreshold);
+        assert_noop!(
+            Bridge::remove_relayer(Origin::root(), RELAYER_A),
+            Error::<Test>::RelayerInvalid
+        );
+
+        assert_ok!(Bridge::set_threshold(Origin::root(), TEST_THRESHOLD));
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_A));
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_B));
+        assert_eq!(Bridge::relayer_threshold(), DEFAULT_RELAYER_THRESHOLD);
+        assert_eq!(Bridge::relayer_count(), 0);
+        assert_eq!(Bridge::scheduled_admin_action(1), Some((6, AdminAction::AddRelayer(RELAYER_A))));
+        assert_events(vec![
+            Event::bridge(RawEvent::AdminActionScheduled(0, AdminAction::SetThreshold(TEST_THRESHOLD), 6)),
+            Event::bridge(RawEvent::AdminActionScheduled(1, AdminAction::AddRelayer(RELAYER_A), 6)),
+            Event::bridge(RawEvent::AdminActionScheduled(2, AdminAction::AddRelayer(RELAYER_B), 6)),
+        ]);
+
+        // Only the veto origin can cancel a scheduled change
+        assert_noop!(Bridge::veto_admin_action(Origin::root(), 2), DispatchError::BadOrigin);
+        assert_noop!(
+            Bridge::veto_admin_action(Origin::signed(RELAYER_A), 2),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Bridge::veto_admin_action(veto.clone(), 2));
+        assert_noop!(
+            Bridge::veto_admin_action(veto.clone(), 2),
+            Error::<Test>::AdminActionNotScheduled
+        );
+        assert_events(vec![Event::bridge(RawEvent::AdminActionVetoed(2))]);
+
+        run_to_block_with_pruning(5);
+        assert_eq!(Bridge::relayer_count(), 0);
+
+        run_to_block_with_pruning(6);
+        assert_eq!(Bridge::relayer_threshold(), TEST_THRESHOLD);
+        assert!(Bridge::is_relayer(&RELAYER_A));
+        assert!(!Bridge::is_relayer(&RELAYER_B));
+        assert_eq!(Bridge::scheduled_admin_action(0), None);
+        assert!(!<AdminActionQueue<Test>>::contains_key(6));
+        assert_events(vec![
+            Event::bridge(RawEvent::RelayerThresholdChanged(TEST_THRESHOLD)),
+            Event::bridge(RawEvent::AdminActionApplied(0, Ok(()))),
+            Event::bridge(RawEvent::RelayerAdded(RELAYER_A)),
+            Event::bridge(RawEvent::AdminActionApplied(1, Ok(()))),
+        ]);
+
+        // Changes are checked again when applied
+        assert_ok!(Bridge::remove_relayer(Origin::root(), RELAYER_A));
+        assert_ok!(Bridge::remove_relayer(Origin::root(), RELAYER_A));
+        run_to_block_with_pruning(11);
+        assert!(!Bridge::is_relayer(&RELAYER_A));
+        assert_events(vec![
+            Event::bridge(RawEvent::RelayerRemoved(RELAYER_A)),
+            Event::bridge(RawEvent::AdminActionApplied(3, Ok(()))),
+            Event::bridge(RawEvent::AdminActionApplied(
+                4,
+                Err(event_error(Error::<Test>::RelayerInvalid)),
+            )),
+        ]);
+    })
+}
+
+#[test]
+fn threshold_fraction_of_active_relayers() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id as u8, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, remark_resource()).execute_with(|| {
+        let proposal = make_proposal(vec![10]);
+        let prop_hash = proposal_hash(&proposal);
+        let vote = |who: u64, nonce: DepositNonce, approve: bool| {
+            let prop = ProposalOrHash::Proposal(Box::new(proposal.clone()));
+            if approve {
+                Bridge::acknowledge_proposal(Origin::signed(who), nonce, src_id, r_id, prop)
+            } else {
+                Bridge::reject_proposal(Origin::signed(who), nonce, src_id, r_id, prop)
+            }
+        };
+
+        // USER is registered but never becomes active, so an absolute threshold over the whole
+        // set can't be reached by the active relayers
+        assert_ok!(Bridge::add_relayer(Origin::root(), USER));
+        assert_ok!(Bridge::set_threshold(Origin::root(), 4));
+        activate_relayers();
+        assert_eq!(Bridge::vote_threshold(src_id, 3), (4, 3));
+        assert_eq!(Bridge::effective_threshold(src_id), 4);
+        assert_ok!(vote(RELAYER_A, 1, true));
+        assert_ok!(vote(RELAYER_B, 1, true));
+        assert_ok!(vote(RELAYER_C, 1, true));
+        assert_eq!(
+            Bridge::votes((src_id, 1), prop_hash).unwrap().status,
+            ProposalStatus::Initiated
+        );
+
+        assert_noop!(
+            Bridge::set_threshold_fraction(Origin::root(), Some(Perbill::from_percent(0))),
+            Error::<Test>::InvalidThreshold
+        );
+        assert_ok!(Bridge::set_threshold_fraction(
+            Origin::root(),
+            Some(Perbill::from_percent(60))
+        ));
+        assert_eq!(Bridge::vote_threshold(src_id, 3), (2, 3));
+        // Only the active relayers count towards the fraction
+        assert_eq!(Bridge::effective_threshold(src_id), 2);
+        assert_events(vec![Event::bridge(RawEvent::RelayerThresholdFractionChanged(Some(
+            Perbill::from_percent(60)
+        )))]);
+
+        assert_ok!(vote(RELAYER_A, 2, true));
+        assert_ok!(vote(RELAYER_B, 2, true));
+        assert_eq!(
+            Bridge::votes((src_id, 2), prop_hash).unwrap().status,
+            ProposalStatus::Executed
+        );
+
+        // With every active relayer required, a single rejection is enough
+        assert_ok!(Bridge::set_threshold_fraction(
+            Origin::root(),
+            Some(Perbill::from_percent(100))
+        ));
+        assert_eq!(Bridge::vote_threshold(src_id, 3), (3, 3));
+        assert_ok!(vote(RELAYER_A, 3, true));
+        assert_ok!(vote(RELAYER_B, 3, false));
+        assert_eq!(
+            Bridge::votes((src_id, 3), prop_hash).unwrap().status,
+            ProposalStatus::Rejected
+        );
+
+        assert_ok!(Bridge::set_threshold_fraction(Origin::root(), None));
+        assert_eq!(Bridge::vote_threshold(src_id, 3), (4, 3));
+    })
+}
+
+#[test]
+fn votes_use_voter_snapshot() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id as u8, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, remark_resource()).execute_with(|| {
+        let proposal = make_proposal(vec![10]);
+        let prop_hash = proposal_hash(&proposal);
+        let vote = |who: u64, nonce: DepositNonce| {
+            let prop = ProposalOrHash::Proposal(Box::new(proposal.clone()));
+            Bridge::acknowledge_proposal(Origin::signed(who), nonce, src_id, r_id, prop)
+        };
+        activate(&[RELAYER_A, RELAYER_C]);
+        assert_ok!(vote(RELAYER_A, 1));
+        assert_eq!(
+            Bridge::votes((src_id, 1), prop_hash).unwrap().voters,
+            vec![RELAYER_A, RELAYER_C]
+        );
+
+        // B became active after the proposal was created, C wasn't elected again
+        activate(&[RELAYER_A, RELAYER_B]);
+        assert_eq!(Bridge::eligible_voters(), vec![RELAYER_A, RELAYER_B]);
+
+        assert_noop!(vote(RELAYER_B, 1), Error::<Test>::MustBeActiveRelayer);
+        assert_ok!(vote(RELAYER_C, 1));
+        assert_eq!(
+            Bridge::votes((src_id, 1), prop_hash).unwrap().status,
+            ProposalStatus::Executed
+        );
+
+        // New proposals use the current active set
+        assert_ok!(vote(RELAYER_B, 2));
+        assert_noop!(vote(RELAYER_C, 2), Error::<Test>::MustBeActiveRelayer);
+        assert_ok!(vote(RELAYER_A, 2));
+        assert_eq!(
+            Bridge::votes((src_id, 2), prop_hash).unwrap().status,
+            ProposalStatus::Executed
+        );
+    })
+}
+
+#[test]
+fn batch_voting() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id as u8, b"remark");
+    let bad_r_id = derive_resource_id(src_id as u8, b"unknown");
+
+    new_test_ext_initialized(src_id, r_id, remark_resource()).execute_with(|| {
+        let proposal = make_proposal(vec![10]);
+        let prop_hash = proposal_hash(&proposal);
+        let full = || ProposalOrHash::Proposal(Box::new(proposal.clone()));
+        activate_relayers();
+
+        assert_noop!(
+            Bridge::acknowledge_proposals_batch(Origin::signed(USER), vec![(1, src_id, r_id, full())]),
+            Error::<Test>::MustBeRelayer
+        );
+        assert_noop!(
+            Bridge::acknowledge_proposals_batch(
+                Origin::signed(RELAYER_A),
+                (1..=MaxVotesPerBatch::get() as u64 + 1).map(|n| (n, src_id, r_id, full())).collect()
+            ),
+            Error::<Test>::TooManyVotes
+        );
+
+        // Failing votes are reported without aborting the batch
+        assert_ok!(Bridge::acknowledge_proposals_batch(
+            Origin::signed(RELAYER_A),
+            vec![
+                (1, src_id, r_id, full()),
+                (2, src_id, bad_r_id, full()),
+                (3, src_id, r_id, ProposalOrHash::Hash(prop_hash)),
+                (4, src_id, r_id, full()),
+            ]
+        ));
+        assert_events(vec![
+            Event::bridge(RawEvent::VoteFor(src_id, 1, RELAYER_A)),
+            Event::bridge(RawEvent::BatchVoteProcessed(src_id, 1, Ok(()))),
+            Event::bridge(RawEvent::BatchVoteProcessed(
+                src_id,
+                2,
+                Err(event_error(Error::<Test>::ResourceDoesNotExist)),
+            )),
+            Event::bridge(RawEvent::BatchVoteProcessed(
+                src_id,
+                3,
+                Err(event_error(Error::<Test>::ProposalPreimageMissing)),
+            )),
+            Event::bridge(RawEvent::VoteFor(src_id, 4, RELAYER_A)),
+            Event::bridge(RawEvent::BatchVoteProcessed(src_id, 4, Ok(()))),
+        ]);
+        assert!(Bridge::votes((src_id, 3), prop_hash).is_none());
+
+        // A vote by hash executes the proposal it approves
+        assert_ok!(Bridge::acknowledge_proposals_batch(
+            Origin::signed(RELAYER_B),
+            vec![(1, src_id, r_id, ProposalOrHash::Hash(prop_hash))]
+        ));
+        assert_eq!(
+            Bridge::votes((src_id, 1), prop_hash).unwrap().status,
+            ProposalStatus::Executed
+        );
+
+        assert_ok!(Bridge::reject_proposals_batch(
+            Origin::signed(RELAYER_B),
+            vec![
+                (1, src_id, r_id, ProposalOrHash::Hash(prop_hash)),
+                (4, src_id, r_id, ProposalOrHash::Hash(prop_hash)),
+            ]
+        ));
+        assert_ok!(Bridge::reject_proposals_batch(
+            Origin::signed(RELAYER_C),
+            vec![(4, src_id, r_id, ProposalOrHash::Hash(prop_hash))]
+        ));
+        assert_eq!(
+            Bridge::votes((src_id, 4), prop_hash).unwrap().status,
+            ProposalStatus::Rejected
+        );
+        assert_events(vec![
+            Event::bridge(RawEvent::VoteAgainst(src_id, 4, RELAYER_C)),
+            Event::bridge(RawEvent::ProposalRejected(src_id, 4)),
+            Event::bridge(RawEvent::BatchVoteProcessed(src_id, 4, Ok(()))),
+        ]);
+    })
+}
+
+#[test]
+fn batch_vote_by_hash_is_charged_for_the_call() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id as u8, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, remark_resource()).execute_with(|| {
+        let proposal = make_proposal(vec![10]);
+        let prop_hash = proposal_hash(&proposal);
+        let by_hash = vec![(1, src_id, r_id, ProposalOrHash::Hash(prop_hash))];
+        activate_relayers();
+
+        // The call isn't known yet, so only the vote itself is charged
+        let base_weight = Bridge::batch_vote_weight(&by_hash, true);
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            1,
+            src_id,
+            r_id,
+            ProposalOrHash::Proposal(Box::new(proposal.clone()))
+        ));
+
+        let call_weight = proposal.get_dispatch_info().weight;
+        assert_eq!(Bridge::batch_vote_weight(&by_hash, true), base_weight + call_weight);
+
+        let post_info = Bridge::acknowledge_proposals_batch(Origin::signed(RELAYER_B), by_hash.clone())
+            .unwrap();
+        assert_eq!(post_info.actual_weight, Some(base_weight + call_weight));
+        assert_eq!(post_info.pays_fee, Pays::No);
+        assert_eq!(
+            Bridge::votes((src_id, 1), prop_hash).unwrap().status,
+            ProposalStatus::Executed
+        );
+    })
+}
+
+#[test]
+fn oversized_batch_is_charged_as_a_full_batch() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id as u8, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, remark_resource()).execute_with(|| {
+        let proposal = make_proposal(vec![10]);
+        let full = || ProposalOrHash::Proposal(Box::new(proposal.clone()));
+        let max = MaxVotesPerBatch::get() as u64;
+
+        let votes: Vec<_> = (1..=max).map(|n| (n, src_id, r_id, full())).collect();
+        let full_batch = Bridge::batch_vote_weight(&votes, true);
+        let full_reject_batch = Bridge::batch_vote_weight(&votes, false);
+        assert!(full_batch > full_reject_batch);
+        assert!(full_reject_batch > 0);
+
+        let votes: Vec<_> = (1..=max + 1).map(|n| (n, src_id, r_id, full())).collect();
+        assert_eq!(Bridge::batch_vote_weight(&votes, true), full_batch);
+        assert_eq!(Bridge::batch_vote_weight(&votes, false), full_reject_batch);
+    })
+}
+
+#[test]
+fn accepted_votes_are_free() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id as u8, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, remark_resource()).execute_with(|| {
+        let proposal = make_proposal(vec![10]);
+        let prop = ProposalOrHash::Proposal(Box::new(proposal.clone()));
+        let relayers = Bridge::relayer_count();
+        let ack_weight = <Test as Trait>::WeightInfo::acknowledge_proposal(
+            relayers,
+            prop.encode().len() as u32,
+        );
+        let reject_weight =
+            <Test as Trait>::WeightInfo::reject_proposal(relayers, prop.encode().len() as u32);
+        activate_relayers();
+
+        // The proposed call isn't executed by the first vote, so its weight is refunded
+        let post_info = Bridge::acknowledge_proposal(Origin::signed(RELAYER_A), 1, src_id, r_id, prop.clone())
+            .unwrap();
+        assert_eq!(post_info.pays_fee, Pays::No);
+        assert_eq!(post_info.actual_weight, Some(ack_weight));
+
+        let post_info = Bridge::reject_proposal(Origin::signed(RELAYER_B), 1, src_id, r_id, prop.clone())
+            .unwrap();
+        assert_eq!(post_info.pays_fee, Pays::No);
+        assert_eq!(post_info.actual_weight, Some(reject_weight));
+
+        // Duplicate votes pay
+        let err = Bridge::acknowledge_proposal(Origin::signed(RELAYER_A), 1, src_id, r_id, prop.clone())
+            .unwrap_err();
+        assert_eq!(err.post_info.pays_fee, Pays::Yes);
+        assert_eq!(err.error, Error::<Test>::RelayerAlreadyVoted.into());
+
+        // The executing vote is charged the weight the call actually used
+        let post_info = Bridge::acknowledge_proposal(Origin::signed(RELAYER_C), 1, src_id, r_id, prop.clone())
+            .unwrap();
+        assert_eq!(post_info.pays_fee, Pays::No);
+        assert_eq!(
+            post_info.actual_weight,
+            Some(ack_weight + proposal.get_dispatch_info().weight)
+        );
+
+        // Votes on a completed proposal pay
+        let err = Bridge::acknowledge_proposal(Origin::signed(RELAYER_B), 1, src_id, r_id, prop.clone())
+            .unwrap_err();
+        assert_eq!(err.post_info.pays_fee, Pays::Yes);
+
+        // A batch is only free if all of its votes are accepted
+        let post_info = Bridge::acknowledge_proposals_batch(
+            Origin::signed(RELAYER_A),
+            vec![(2, src_id, r_id, prop.clone()), (3, src_id, r_id, prop.clone())],
+        )
+        .unwrap();
+        let batch_weight = <Test as Trait>::WeightInfo::acknowledge_proposals_batch(
+            2,
+            relayers,
+            2 * prop.encode().len() as u32,
+        );
+        assert_eq!(post_info.pays_fee, Pays::No);
+        assert_eq!(post_info.actual_weight, Some(batch_weight));
+
+        let post_info = Bridge::acknowledge_proposals_batch(
+            Origin::signed(RELAYER_A),
+            vec![(2, src_id, r_id, prop.clone()), (4, src_id, r_id, prop.clone())],
+        )
+        .unwrap();
+        assert_eq!(post_info.pays_fee, Pays::Yes);
+    })
+}
+
+#[test]
+fn outbound_deposits_are_recorded() {
+    let dest_id = 2;
+    let resource_id = [1; 32];
+    let nft_resource_id = [2; 32];
+    let generic_resource_id = [3; 32];
+
+    new_test_ext().execute_with(|| {
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_id));
+        register_outbound_resource(resource_id, ResourceKind::Fungible { decimals: 18 });
+        register_outbound_resource(nft_resource_id, ResourceKind::NonFungible);
+        register_outbound_resource(generic_resource_id, ResourceKind::Generic);
+
+        assert_ok!(Bridge::transfer_fungible(&USER, dest_id, resource_id, vec![1], 10.into()));
+        assert_ok!(Bridge::transfer_nonfungible(
+            &USER,
+            dest_id,
+            nft_resource_id,
+            vec![2],
+            vec![1],
+            vec![3]
+        ));
+        run_to_block(2);
+        assert_ok!(Bridge::transfer_generic(&USER, dest_id, generic_resource_id, vec![4]));
+
+        assert_eq!(
+            Bridge::deposit_record(dest_id, 1),
+            Some(DepositRecord {
+                resource_id,
+                payload: DepositPayload::Fungible(10.into(), vec![1]),
+                block: 1,
+            })
+        );
+        assert_eq!(
+            Bridge::deposit_record(dest_id, 2).unwrap().payload,
+            DepositPayload::NonFungible(vec![2], vec![1], vec![3])
+        );
+        assert_eq!(
+            Bridge::deposit_record(dest_id, 3),
+            Some(DepositRecord {
+                resource_id: generic_resource_id,
+                payload: DepositPayload::Generic(vec![4]),
+                block: 2,
+            })
+        );
+
+        // Only the latest `DepositRetention` deposits are kept
+        assert_ok!(Bridge::transfer_generic(&USER, dest_id, generic_resource_id, vec![5]));
+        assert_eq!(Bridge::deposit_record(dest_id, 1), None);
+        assert!(Bridge::deposit_record(dest_id, 4).is_some());
+
+        let nonces = |records: Vec<(DepositNonce, DepositRecord<u64>)>| {
+            records.into_iter().map(|(nonce, _)| nonce).collect::<Vec<_>>()
+        };
+        assert_eq!(nonces(Bridge::deposit_records(dest_id, 2, 10)), vec![2, 3, 4]);
+        assert_eq!(nonces(Bridge::deposit_records(dest_id, 1, 10)), vec![2, 3]);
+        assert_eq!(nonces(Bridge::deposit_records(dest_id, 3, 10)), vec![3, 4]);
+        assert_eq!(nonces(Bridge::deposit_records(dest_id, 2, 1)), vec![2]);
+        assert_eq!(nonces(Bridge::deposit_records(dest_id + 1, 1, 10)), Vec::<DepositNonce>::new());
+    })
+}
+
+#[test]
+fn outbound_transfers_are_rate_limited() {
+    let dest_id = 2;
+    let resource_id = [1; 32];
+    let other_resource_id = [2; 32];
+    let limit = RateLimit {
+        window: 10,
+        max_amount: Some(100.into()),
+        max_count: Some(3),
+    };
+
+    new_test_ext().execute_with(|| {
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_id));
+        register_outbound_resource(resource_id, ResourceKind::Fungible { decimals: 18 });
+        register_outbound_resource(other_resource_id, ResourceKind::Fungible { decimals: 18 });
+        assert_noop!(
+            Bridge::set_rate_limit(
+                Origin::signed(RELAYER_A),
+                BridgeDirection::Outbound,
+                dest_id,
+                resource_id,
+                Some(limit.clone())
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Bridge::set_rate_limit(
+                Origin::root(),
+                BridgeDirection::Outbound,
+                dest_id,
+                resource_id,
+                Some(RateLimit { window: 0, ..limit.clone() })
+            ),
+            Error::<Test>::InvalidRateLimit
+        );
+        assert_ok!(Bridge::set_rate_limit(
+            Origin::root(),
+            BridgeDirection::Outbound,
+            dest_id,
+            resource_id,
+            Some(limit.clone())
+        ));
+        assert_events(vec![Event::bridge(RawEvent::RateLimitChanged(
+            BridgeDirection::Outbound,
+            dest_id,
+            resource_id,
+            Some(limit.clone()),
+        ))]);
+
+        // The amount is capped
+        assert_ok!(Bridge::transfer_fungible(&USER, dest_id, resource_id, vec![1], 60.into()));
+        assert_noop!(
+            Bridge::transfer_fungible(&USER, dest_id, resource_id, vec![1], 50.into()),
+            Error::<Test>::RateLimitExceeded
+        );
+        run_to_block(5);
+        assert_ok!(Bridge::transfer_fungible(&USER, dest_id, resource_id, vec![1], 40.into()));
+
+        // So is the number of transfers
+        assert_ok!(Bridge::transfer_fungible(&USER, dest_id, resource_id, vec![1], 0.into()));
+        assert_noop!(
+            Bridge::transfer_fungible(&USER, dest_id, resource_id, vec![1], 0.into()),
+            Error::<Test>::RateLimitExceeded
+        );
+
+        // Other resources aren't limited
+        assert_ok!(Bridge::transfer_fungible(&USER, dest_id, other_resource_id, vec![1], 500.into()));
+
+        // Transfers are counted until the window has passed since they were made
+        run_to_block(11);
+        assert_noop!(
+            Bridge::transfer_fungible(&USER, dest_id, resource_id, vec![1], 70.into()),
+            Error::<Test>::RateLimitExceeded
+        );
+        assert_ok!(Bridge::transfer_fungible(&USER, dest_id, resource_id, vec![1], 60.into()));
+
+        // Removing the limit clears its usage
+        assert_ok!(Bridge::set_rate_limit(
+            Origin::root(),
+            BridgeDirection::Outbound,
+            dest_id,
+            resource_id,
+            None
+        ));
+        assert_eq!(Bridge::rate_limit((BridgeDirection::Outbound, dest_id, resource_id)), None);
+        assert_eq!(
+            Bridge::rate_limit_usage((BridgeDirection::Outbound, dest_id, resource_id)),
+            vec![]
+        );
+        assert_ok!(Bridge::transfer_fungible(&USER, dest_id, resource_id, vec![1], 500.into()));
+    })
+}
+
+#[test]
+fn approved_proposals_are_rate_limited() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id as u8, b"remark");
+    let other_r_id = derive_resource_id(src_id as u8, b"other");
+
+    new_test_ext_initialized(src_id, r_id, remark_resource()).execute_with(|| {
+        let proposal = make_proposal(vec![10]);
+        let prop_hash = proposal_hash(&proposal);
+        let prop = ProposalOrHash::Proposal(Box::new(proposal.clone()));
+        assert_ok!(Bridge::set_resource(Origin::root(), other_r_id, remark_resource()));
+        assert_ok!(Bridge::set_rate_limit(
+            Origin::root(),
+            BridgeDirection::Inbound,
+            src_id,
+            r_id,
+            Some(RateLimit {
+                window: 10,
+                max_amount: None,
+                max_count: Some(1),
+            })
+        ));
+        activate_relayers();
+
+        assert_ok!(Bridge::acknowledge_proposal(Origin::signed(RELAYER_A), 1, src_id, r_id, prop.clone()));
+        assert_ok!(Bridge::acknowledge_proposal(Origin::signed(RELAYER_B), 1, src_id, r_id, prop.clone()));
+        assert_eq!(
+            Bridge::votes((src_id, 1), prop_hash).unwrap().status,
+            ProposalStatus::Executed
+        );
+
+        // The vote that would approve a second proposal is rejected and not recorded
+        assert_ok!(Bridge::acknowledge_proposal(Origin::signed(RELAYER_A), 2, src_id, r_id, prop.clone()));
+        assert_noop!(
+            Bridge::acknowledge_proposal(Origin::signed(RELAYER_B), 2, src_id, r_id, prop.clone()),
+            Error::<Test>::RateLimitExceeded
+        );
+        let votes = Bridge::votes((src_id, 2), prop_hash).unwrap();
+        assert_eq!(votes.votes_for, vec![RELAYER_A]);
+        assert_eq!(votes.status, ProposalStatus::Initiated);
+
+        // Votes must reference the resource the proposal was created with
+        assert_noop!(
+            Bridge::acknowledge_proposal(Origin::signed(RELAYER_B), 2, src_id, other_r_id, prop.clone()),
+            Error::<Test>::ResourceMismatch
+        );
+
+        assert_ok!(Bridge::set_rate_limit(Origin::root(), BridgeDirection::Inbound, src_id, r_id, None));
+        assert_ok!(Bridge::acknowledge_proposal(Origin::signed(RELAYER_B), 2, src_id, r_id, prop.clone()));
+        assert_eq!(
+            Bridge::votes((src_id, 2), prop_hash).unwrap().status,
+            ProposalStatus::Executed
+        );
+    })
+}
+
+#[test]
+fn outbound_transfers_match_resource() {
+    let dest_id = 2;
+    let resource_id = [1; 32];
+
+    new_test_ext().execute_with(|| {
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_id));
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_id + 1));
+        assert_noop!(
+            Bridge::transfer_fungible(&USER, dest_id, resource_id, vec![1], 10.into()),
+            Error::<Test>::ResourceDoesNotExist
+        );
+
+        let mut resource = make_resource(
+            ResourceKind::Fungible { decimals: 18 },
+            b"Pallet.transfer",
+            &make_proposal(vec![]),
+        );
+        resource.destinations = vec![dest_id];
+        assert_ok!(Bridge::set_resource(Origin::root(), resource_id, resource.clone()));
+
+        assert_ok!(Bridge::transfer_fungible(&USER, dest_id, resource_id, vec![1], 10.into()));
+        assert_noop!(
+            Bridge::transfer_generic(&USER, dest_id, resource_id, vec![1]),
+            Error::<Test>::ResourceKindMismatch
+        );
+        assert_noop!(
+            Bridge::transfer_nonfungible(&USER, dest_id, resource_id, vec![1], vec![1], vec![]),
+            Error::<Test>::ResourceKindMismatch
+        );
+        assert_noop!(
+            Bridge::transfer_fungible(&USER, dest_id + 1, resource_id, vec![1], 10.into()),
+            Error::<Test>::DestinationNotAllowed
+        );
+
+        resource.enabled = false;
+        assert_ok!(Bridge::set_resource(Origin::root(), resource_id, resource));
+        assert_noop!(
+            Bridge::transfer_fungible(&USER, dest_id, resource_id, vec![1], 10.into()),
+            Error::<Test>::ResourceDisabled
+        );
+    })
+}
+
+#[test]
+fn proposals_match_resource_call() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id as u8, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, remark_resource()).execute_with(|| {
+        activate_relayers();
+
+        // The resource only accepts `System.remark` calls
+        let proposal = Call::System(system::Call::set_heap_pages(1));
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_A),
+                1,
+                src_id,
+                r_id,
+                ProposalOrHash::Proposal(Box::new(proposal))
+            ),
+            Error::<Test>::CallMismatch
+        );
+
+        let prop = ProposalOrHash::Proposal(Box::new(make_proposal(vec![10])));
+        assert_ok!(Bridge::acknowledge_proposal(Origin::signed(RELAYER_A), 1, src_id, r_id, prop.clone()));
+
+        let resource = Resource { enabled: false, ..remark_resource() };
+        assert_ok!(Bridge::set_resource(Origin::root(), r_id, resource));
+        assert_noop!(
+            Bridge::acknowledge_proposal(Origin::signed(RELAYER_B), 1, src_id, r_id, prop),
+            Error::<Test>::ResourceDisabled
+        );
+    })
+}
+
+#[test]
+fn chain_config_validates_recipients() {
+    let dest_id = 2;
+    let resource_id = [1; 32];
+    let nft_resource_id = [2; 32];
+    let config = ChainConfig {
+        name: b"Ethereum".to_vec(),
+        proposal_lifetime: None,
+        address_format: AddressFormat::Length(20),
+    };
+
+    new_test_ext().execute_with(|| {
+        register_outbound_resource(resource_id, ResourceKind::Fungible { decimals: 18 });
+        register_outbound_resource(nft_resource_id, ResourceKind::NonFungible);
+        assert_noop!(
+            Bridge::set_chain_config(Origin::root(), dest_id, Some(config.clone())),
+            Error::<Test>::ChainNotWhitelisted
+        );
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_id));
+        assert_noop!(
+            Bridge::set_chain_config(Origin::signed(USER), dest_id, Some(config.clone())),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Bridge::set_chain_config(
+                Origin::root(),
+                dest_id,
+                Some(ChainConfig {
+                    address_format: AddressFormat::LengthRange(32, 20),
+                    ..config.clone()
+                })
+            ),
+            Error::<Test>::InvalidChainConfig
+        );
+
+        // Recipients aren't checked until the chain is configured
+        assert_ok!(Bridge::transfer_fungible(&USER, dest_id, resource_id, vec![1], 10.into()));
+
+        assert_ok!(Bridge::set_chain_config(Origin::root(), dest_id, Some(config.clone())));
+        assert_eq!(Bridge::chain_config(dest_id), Some(config.clone()));
+        assert_events(vec![Event::bridge(RawEvent::ChainConfigChanged(dest_id, Some(config)))]);
+
+        assert_noop!(
+            Bridge::transfer_fungible(&USER, dest_id, resource_id, vec![1; 19], 10.into()),
+            Error::<Test>::InvalidRecipient
+        );
+        assert_noop!(
+            Bridge::transfer_nonfungible(&USER, dest_id, nft_resource_id, vec![1], vec![1; 32], vec![]),
+            Error::<Test>::InvalidRecipient
+        );
+        assert_ok!(Bridge::transfer_fungible(&USER, dest_id, resource_id, vec![1; 20], 10.into()));
+        assert_ok!(Bridge::transfer_nonfungible(&USER, dest_id, nft_resource_id, vec![1], vec![1; 20], vec![]));
+        assert_eq!(Bridge::chains(dest_id), Some(3));
+
+        assert_ok!(Bridge::set_chain_config(Origin::root(), dest_id, None));
+        assert_eq!(Bridge::chain_config(dest_id), None);
+        assert_ok!(Bridge::transfer_fungible(&USER, dest_id, resource_id, vec![1], 10.into()));
+    })
+}
+
+#[test]
+fn chain_config_overrides_lifetime_and_threshold() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id as u8, b"remark");
+    let lifetime = 5;
+
+    new_test_ext_initialized(src_id, r_id, remark_resource()).execute_with(|| {
+        let proposal = make_proposal(vec![10]);
+        let prop_hash = proposal_hash(&proposal);
+        let prop = ProposalOrHash::Proposal(Box::new(proposal.clone()));
+        assert_noop!(
+            Bridge::set_chain_config(
+                Origin::root(),
+                src_id,
+                Some(ChainConfig {
+                    name: b"TON".to_vec(),
+                    proposal_lifetime: Some(0),
+                    address_format: AddressFormat::Any,
+                })
+            ),
+            Error::<Test>::InvalidChainConfig
+        );
+        assert_ok!(Bridge::set_chain_config(
+            Origin::root(),
+            src_id,
+            Some(ChainConfig {
+                name: b"TON".to_vec(),
+                proposal_lifetime: Some(lifetime),
+                address_format: AddressFormat::Any,
+            })
+        ));
+
+        // The threshold override is an admin change, applied after the admin delay
+        AdminDelay::set(5);
+        assert_noop!(
+            Bridge::set_chain_threshold(Origin::signed(USER), src_id, Some(3)),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Bridge::set_chain_threshold(Origin::root(), src_id + 1, Some(3)),
+            Error::<Test>::ChainNotWhitelisted
+        );
+        assert_noop!(
+            Bridge::set_chain_threshold(Origin::root(), src_id, Some(0)),
+            Error::<Test>::InvalidThreshold
+        );
+        assert_ok!(Bridge::set_chain_threshold(Origin::root(), src_id, Some(3)));
+        assert_eq!(Bridge::chain_threshold(src_id), None);
+        assert_events(vec![Event::bridge(RawEvent::AdminActionScheduled(
+            0,
+            AdminAction::SetChainThreshold(src_id, Some(3)),
+            System::block_number() + 5,
+        ))]);
+
+        run_to_block_with_pruning(System::block_number() + 5);
+        assert_eq!(Bridge::chain_threshold(src_id), Some(3));
+        assert_events(vec![
+            Event::bridge(RawEvent::ChainThresholdChanged(src_id, Some(3))),
+            Event::bridge(RawEvent::AdminActionApplied(0, Ok(()))),
+        ]);
+        assert_eq!(Bridge::vote_threshold(src_id, 3), (3, 3));
+        assert_eq!(Bridge::vote_threshold(src_id + 1, 3), (TEST_THRESHOLD, 3));
+        activate_relayers();
+        assert_eq!(Bridge::effective_threshold(src_id), 3);
+        assert_eq!(Bridge::effective_threshold(src_id + 1), TEST_THRESHOLD);
+
+        assert_ok!(Bridge::acknowledge_proposal(Origin::signed(RELAYER_A), 1, src_id, r_id, prop.clone()));
+        assert_ok!(Bridge::acknowledge_proposal(Origin::signed(RELAYER_B), 1, src_id, r_id, prop.clone()));
+        let votes = Bridge::votes((src_id, 1), prop_hash).unwrap();
+        assert_eq!(votes.expiry, System::block_number() + lifetime);
+        assert_eq!(votes.status, ProposalStatus::Initiated);
+
+        assert_ok!(Bridge::acknowledge_proposal(Origin::signed(RELAYER_C), 1, src_id, r_id, prop));
+        assert_eq!(
+            Bridge::votes((src_id, 1), prop_hash).unwrap().status,
+            ProposalStatus::Executed
+        );
+    })
+}
+
+#[test]
+fn disable_and_enable_chain() {
+    let dest_id = 2;
+    let resource_id = [1; 32];
+
+    new_test_ext().execute_with(|| {
+        register_outbound_resource(resource_id, ResourceKind::Generic);
+        assert_noop!(
+            Bridge::disable_chain(Origin::root(), dest_id),
+            Error::<Test>::ChainNotWhitelisted
+        );
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_id));
+        assert_ok!(Bridge::transfer_generic(&USER, dest_id, resource_id, vec![]));
+
+        assert_noop!(
+            Bridge::disable_chain(Origin::signed(USER), dest_id),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Bridge::enable_chain(Origin::root(), dest_id),
+            Error::<Test>::ChainNotDisabled
+        );
+        assert_ok!(Bridge::disable_chain(Origin::root(), dest_id));
+        assert_noop!(
+            Bridge::disable_chain(Origin::root(), dest_id),
+            Error::<Test>::ChainAlreadyDisabled
+        );
+        assert!(!Bridge::chain_whitelisted(dest_id));
+        assert_eq!(Bridge::whitelisted_chains(), vec![(dest_id, 1)]);
+
+        // A disabled chain can't be whitelisted again, only enabled
+        assert_noop!(
+            Bridge::whitelist_chain(Origin::root(), dest_id),
+            Error::<Test>::ChainAlreadyWhitelisted
+        );
+        assert_noop!(
+            Bridge::transfer_generic(&USER, dest_id, resource_id, vec![]),
+            Error::<Test>::ChainDisabled
+        );
+
+        // The nonce continues where it left off
+        assert_ok!(Bridge::enable_chain(Origin::root(), dest_id));
+        assert!(Bridge::chain_whitelisted(dest_id));
+        assert_ok!(Bridge::transfer_generic(&USER, dest_id, resource_id, vec![]));
+
+        assert_events(vec![
+            Event::bridge(RawEvent::ChainDisabled(dest_id)),
+            Event::bridge(RawEvent::ChainEnabled(dest_id)),
+            Event::bridge(RawEvent::GenericTransfer(dest_id, 2, resource_id, vec![])),
+        ]);
+    })
+}
+
+#[test]
+fn eval_vote_state_respects_resource_pause() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id as u8, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, remark_resource()).execute_with(|| {
+        let proposal = make_proposal(vec![10]);
+        let prop_hash = proposal_hash(&proposal);
+        let prop = ProposalOrHash::Proposal(Box::new(proposal.clone()));
+        activate(&[RELAYER_A]);
+
+        assert_ok!(Bridge::acknowledge_proposal(Origin::signed(RELAYER_A), 1, src_id, r_id, prop.clone()));
+        assert_ok!(Bridge::set_threshold(Origin::root(), 1));
+        assert_ok!(Bridge::pause(Origin::root(), PauseScope::Resource(r_id)));
+
+        assert_noop!(
+            Bridge::eval_vote_state(Origin::signed(USER), 1, src_id, prop.clone()),
+            Error::<Test>::ResourcePaused
+        );
+
+        assert_ok!(Bridge::unpause(Origin::root(), PauseScope::Resource(r_id)));
+        assert_ok!(Bridge::eval_vote_state(Origin::signed(USER), 1, src_id, prop));
+        assert_eq!(
+            Bridge::votes((src_id, 1), prop_hash).unwrap().status,
+            ProposalStatus::Executed
+        );
+    })
+}
+
+#[test]
+fn eval_vote_state_checks_the_registered_resource() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id as u8, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, remark_resource()).execute_with(|| {
+        let proposal = make_proposal(vec![10]);
+        let prop_hash = proposal_hash(&proposal);
+        let prop = ProposalOrHash::Proposal(Box::new(proposal.clone()));
+        activate(&[RELAYER_A]);
+
+        assert_ok!(Bridge::acknowledge_proposal(Origin::signed(RELAYER_A), 1, src_id, r_id, prop.clone()));
+        assert_ok!(Bridge::set_threshold(Origin::root(), 1));
+
+        let mut resource = remark_resource();
+        resource.enabled = false;
+        assert_ok!(Bridge::set_resource(Origin::root(), r_id, resource));
+        assert_noop!(
+            Bridge::eval_vote_state(Origin::signed(USER), 1, src_id, prop.clone()),
+            Error::<Test>::ResourceDisabled
+        );
+
+        // The resource now maps to a different call
+        let mut resource = remark_resource();
+        resource.call_index[1] = resource.call_index[1].wrapping_add(1);
+        assert_ok!(Bridge::set_resource(Origin::root(), r_id, resource));
+        assert_noop!(
+            Bridge::eval_vote_state(Origin::signed(USER), 1, src_id, prop.clone()),
+            Error::<Test>::CallMismatch
+        );
+
+        assert_ok!(Bridge::set_resource(Origin::root(), r_id, remark_resource()));
+        assert_ok!(Bridge::eval_vote_state(Origin::signed(USER), 1, src_id, prop));
+        assert_eq!(
+            Bridge::votes((src_id, 1), prop_hash).unwrap().status,
+            ProposalStatus::Executed
+        );
+    })
+}
+
+#[test]
+fn proposals_from_disabled_chain_are_rejected() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id as u8, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, remark_resource()).execute_with(|| {
+        let proposal = make_proposal(vec![10]);
+        let prop_hash = proposal_hash(&proposal);
+        let prop = ProposalOrHash::Proposal(Box::new(proposal.clone()));
+        activate_relayers();
+
+        assert_ok!(Bridge::acknowledge_proposal(Origin::signed(RELAYER_A), 1, src_id, r_id, prop.clone()));
+        assert_ok!(Bridge::disable_chain(Origin::root(), src_id));
+
+        // The pending proposal can't be voted on or evaluated
+        assert_noop!(
+            Bridge::acknowledge_proposal(Origin::signed(RELAYER_B), 1, src_id, r_id, prop.clone()),
+            Error::<Test>::ChainDisabled
+        );
+        assert_noop!(
+            Bridge::reject_proposal(Origin::signed(RELAYER_B), 1, src_id, r_id, prop.clone()),
+            Error::<Test>::ChainDisabled
+        );
+        assert_noop!(
+            Bridge::eval_vote_state(Origin::signed(RELAYER_A), 1, src_id, prop.clone()),
+            Error::<Test>::ChainDisabled
+        );
+
+        // It continues once the chain is enabled again
+        assert_ok!(Bridge::enable_chain(Origin::root(), src_id));
+        assert_ok!(Bridge::acknowledge_proposal(Origin::signed(RELAYER_B), 1, src_id, r_id, prop));
+        assert_eq!(
+            Bridge::votes((src_id, 1), prop_hash).unwrap().status,
+            ProposalStatus::Executed
+        );
+    })
+}
+
+#[test]
+fn migrate_opaque_keys() {
+    use frame_support::{
+        storage::{
+            migration::{get_storage_value, put_storage_value},
+            IterableStorageMap,
+        },
+        traits::OnRuntimeUpgrade,
+    };
+    use sp_io::hashing::blake2_256;
+
+    new_test_ext().execute_with(|| {
+        // Storage as written by the first release, keyed with `opaque_blake2_256`
+        let src_id: u8 = 1;
+        let proposal = make_proposal(vec![10]);
+        put_storage_value(b"ChainBridge", b"ChainNonces", &blake2_256(&src_id.encode()), 5u64);
+        for relayer in &[RELAYER_A, RELAYER_B, RELAYER_C] {
+            put_storage_value(b"ChainBridge", b"Relayers", &blake2_256(&relayer.encode()), true);
+        }
+        <RelayerCount>::put(3);
+        <RelayerThreshold>::put(2);
+        let votes_key = [blake2_256(&src_id.encode()), blake2_256(&(1u64, proposal).encode())].concat();
+        let votes = (vec![RELAYER_B], Vec::<u64>::new(), 0u8, 10u64);
+        put_storage_value(b"ChainBridge", b"Votes", &votes_key, votes);
+
+        // The runtime lists RELAYER_A and RELAYER_B, but not RELAYER_C
+        migrations::migrate_legacy_relayers::<Test>(&[RELAYER_A, RELAYER_B]);
+        Bridge::on_runtime_upgrade();
+
+        assert_eq!(Bridge::chains(1), Some(5));
+        assert!(Bridge::is_relayer(&RELAYER_A));
+        assert!(Bridge::is_relayer(&RELAYER_B));
+        assert!(!Bridge::is_relayer(&RELAYER_C));
+        assert_eq!(<Relayers<Test>>::iter().count(), 2);
+        assert_eq!(Bridge::relayer_count(), 2);
+        // The proposal is removed, so that it can be voted on again
+        let stored: Option<(Vec<u64>, Vec<u64>, u8, u64)> =
+            get_storage_value(b"ChainBridge", b"Votes", &votes_key);
+        assert_eq!(stored, None);
+        assert_eq!(StorageVersion::get(), Releases::current::<Test>());
+
+        // Storage that already has the new layout is kept
+        StorageVersion::kill();
+        Bridge::on_runtime_upgrade();
+        assert_eq!(Bridge::chains(1), Some(5));
+        assert_eq!(<Relayers<Test>>::iter().count(), 2);
+        assert_eq!(Bridge::relayer_count(), 2);
+    })
+}
+
+#[test]
+fn migrate_opaque_keys_keeps_relayers_needed_for_threshold() {
+    use frame_support::{
+        storage::{
+            migration::{get_storage_value, put_storage_value},
+            IterableStorageMap,
+        },
+        traits::OnRuntimeUpgrade,
+    };
+    use sp_io::hashing::blake2_256;
+
+    new_test_ext().execute_with(|| {
+        for relayer in &[RELAYER_A, RELAYER_B, RELAYER_C] {
+            put_storage_value(b"ChainBridge", b"Relayers", &blake2_256(&relayer.encode()), true);
+        }
+        <RelayerCount>::put(3);
+        <RelayerThreshold>::put(2);
+
+        // One migrated relayer can't reach the threshold, so the others and the count are kept
+        migrations::migrate_legacy_relayers::<Test>(&[RELAYER_A]);
+        Bridge::on_runtime_upgrade();
+        assert!(Bridge::is_relayer(&RELAYER_A));
+        assert!(!Bridge::is_relayer(&RELAYER_B));
+        assert_eq!(Bridge::relayer_count(), 3);
+        let stored: Option<bool> =
+            get_storage_value(b"ChainBridge", b"Relayers", &blake2_256(&RELAYER_B.encode()));
+        assert_eq!(stored, Some(true));
+
+        // A later upgrade migrates the rest
+        migrations::migrate_legacy_relayers::<Test>(&[RELAYER_B, RELAYER_C]);
+        assert!(Bridge::is_relayer(&RELAYER_B));
+        assert!(Bridge::is_relayer(&RELAYER_C));
+        assert_eq!(<Relayers<Test>>::iter().count(), 3);
+        assert_eq!(Bridge::relayer_count(), 3);
+    })
+}
+
+#[test]
+fn migrated_executed_nonces_are_not_replayed() {
+    use frame_support::{storage::migration::put_storage_value, traits::OnRuntimeUpgrade};
+    use sp_io::hashing::blake2_256;
+
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id as u8, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, remark_resource()).execute_with(|| {
+        // Nonce 3 was executed and nonce 5 is pending under the keys of the first release
+        let proposal = make_proposal(vec![10]);
+        for (nonce, status) in &[(3u64, 1u8), (5, 0)] {
+            let votes_key =
+                [blake2_256(&(src_id as u8).encode()), blake2_256(&(*nonce, proposal.clone()).encode())].concat();
+            let votes = (vec![RELAYER_A, RELAYER_B], Vec::<u64>::new(), *status, 10u64);
+            put_storage_value(b"ChainBridge", b"Votes", &votes_key, votes);
+        }
+        <ExecutedNonces<Test>>::insert(src_id, 2, true);
+        StorageVersion::kill();
+
+        // The runtime knows that the nonces up to 3 were executed
+        migrations::migrate_executed_nonces::<Test>(&[(src_id, 3)]);
+        Bridge::on_runtime_upgrade();
+        assert_eq!(Bridge::executed_nonce_watermark(src_id), 3);
+        assert!(!<ExecutedNonces<Test>>::contains_key(src_id, 2));
+
+        activate_relayers();
+        let prop = ProposalOrHash::Proposal(Box::new(proposal.clone()));
+        assert_noop!(
+            Bridge::acknowledge_proposal(Origin::signed(RELAYER_A), 3, src_id, r_id, prop.clone()),
+            Error::<Test>::NonceAlreadyExecuted
+        );
+        // The pending proposal is voted on again
+        assert_ok!(Bridge::acknowledge_proposal(Origin::signed(RELAYER_A), 5, src_id, r_id, prop));
+    })
+}
+
+#[test]
+fn migrate_legacy_resources() {
+    use frame_support::{
+        storage::migration::{get_storage_value, put_storage_value},
+        traits::OnRuntimeUpgrade,
+    };
+    use sp_io::hashing::blake2_256;
+
+    new_test_ext().execute_with(|| {
+        // Resources of the first release only stored their method
+        let r_id = derive_resource_id(1, b"remark");
+        let unknown_id = derive_resource_id(1, b"unknown");
+        put_storage_value(b"ChainBridge", b"Resources", &blake2_256(&r_id), b"remark".to_vec());
+        put_storage_value(b"ChainBridge", b"Resources", &blake2_256(&unknown_id), b"unknown".to_vec());
+
+        let call_index = remark_resource().call_index;
+        migrations::migrate_legacy_resources::<Test>(&[(r_id, ResourceKind::Generic, call_index)]);
+        Bridge::on_runtime_upgrade();
+
+        let resource = Bridge::resources(r_id).unwrap();
+        assert_eq!(resource.method, b"remark".to_vec());
+        assert_eq!(resource.kind, ResourceKind::Generic);
+        assert_eq!(resource.call_index, call_index);
+        assert!(resource.destinations.is_empty());
+        assert!(resource.enabled);
+        // Resources the runtime didn't list are removed
+        assert_eq!(Bridge::resources(unknown_id), None);
+        let stored: Option<Vec<u8>> =
+            get_storage_value(b"ChainBridge", b"Resources", &blake2_256(&unknown_id));
+        assert_eq!(stored, None);
+    })
+}
+
+#[test]
+fn migrate_to_nonce_keyed_votes() {
+    use frame_support::{
+        storage::migration::put_storage_value, traits::OnRuntimeUpgrade, Blake2_128Concat, StorageHasher,
+    };
+
+    new_test_ext().execute_with(|| {
+        // Proposals keyed by their chain ID, then by their nonce and hash
+        let src_id: u8 = 1;
+        let r_id = derive_resource_id(src_id, b"remark");
+        let proposal = make_proposal(vec![10]);
+        let prop_hash = proposal_hash(&proposal);
+        let votes = ProposalVotes {
+            votes_for: vec![RELAYER_A],
+            votes_against: vec![],
+            status: ProposalStatus::Initiated,
+            expiry: ProposalLifetime::get(),
+            voters: vec![RELAYER_A, RELAYER_B],
+            resource_id: r_id,
+        };
+        let chain_key = Blake2_128Concat::hash(&src_id.encode());
+        for nonce in 1u64..=2 {
+            let key = [chain_key.clone(), Blake2_128Concat::hash(&(nonce, prop_hash).encode())].concat();
+            put_storage_value(b"ChainBridge", b"Votes", &key, votes.clone());
+            put_storage_value(b"ChainBridge", b"ProposalPreimages", &key, proposal.clone());
+        }
+        StorageVersion::put(Releases::V2NarrowChainIds);
+
+        Bridge::on_runtime_upgrade();
+
+        for nonce in 1..=2 {
+            assert_eq!(Bridge::votes((1, nonce), prop_hash), Some(votes.clone()));
+            assert_eq!(Bridge::proposal_preimage((1, nonce), prop_hash), Some(proposal.clone()));
+        }
+        // A nonce only lists its own proposals
+        assert_eq!(Bridge::proposals(1, 2, None, 10), vec![(prop_hash, votes.clone())]);
+        assert_eq!(StorageVersion::get(), Releases::current::<Test>());
+
+        // Migrations are only applied once
+        <Votes<Test>>::remove((1, 2), prop_hash);
+        Bridge::on_runtime_upgrade();
+        assert_eq!(Bridge::votes((1, 2), prop_hash), None);
+        assert_eq!(Bridge::votes((1, 1), prop_hash), Some(votes));
+    })
+}
+
+#[test]
+fn migrate_to_wide_chain_ids() {
+    use frame_support::{
+        storage::migration::put_storage_value, traits::OnRuntimeUpgrade, Blake2_128Concat, StorageHasher,
+    };
+
+    new_test_ext().execute_with(|| {
+        // Storage as written with `u8` chain IDs
+        let src_id: u8 = 1;
+        let r_id = derive_resource_id(src_id, b"remark");
+        let prop_hash = H256::repeat_byte(7);
+        let votes = ProposalVotes {
+            votes_for: vec![RELAYER_A],
+            votes_against: vec![],
+            status: ProposalStatus::Initiated,
+            expiry: ProposalLifetime::get(),
+            voters: vec![RELAYER_A, RELAYER_B],
+            resource_id: r_id,
+        };
+        let chain_key = Blake2_128Concat::hash(&src_id.encode());
+        let votes_key = [chain_key.clone(), Blake2_128Concat::hash(&(1u64, prop_hash).encode())].concat();
+        put_storage_value(b"ChainBridge", b"ChainNonces", &chain_key, 3u64);
+        put_storage_value(b"ChainBridge", b"Votes", &votes_key, votes.clone());
+        put_storage_value(
+            b"ChainBridge",
+            b"Paused",
+            &Blake2_128Concat::hash(&(2u8, src_id).encode()),
+            true,
+        );
+        put_storage_value(
+            b"ChainBridge",
+            b"Resources",
+            &Blake2_128Concat::hash(&r_id),
+            (b"remark".to_vec(), ResourceKind::Generic, vec![src_id], true, [0u8; 2]),
+        );
+        StorageVersion::put(Releases::V2NarrowChainIds);
+
+        Bridge::on_runtime_upgrade();
+
+        assert_eq!(Bridge::chains(1), Some(3));
+        assert_eq!(Bridge::votes((1, 1), prop_hash), Some(votes.clone()));
+        assert!(Bridge::paused(PauseScope::Chain(1)));
+        assert_eq!(Bridge::resources(r_id).unwrap().destinations, vec![1]);
+        assert_eq!(StorageVersion::get(), Releases::V5WideNonceKeyedVotes);
+
+        // Migrations are only applied once
+        <ChainNonces<Test>>::insert(1, 4);
+        Bridge::on_runtime_upgrade();
+        assert_eq!(Bridge::chains(1), Some(4));
+        assert_eq!(Bridge::votes((1, 1), prop_hash), Some(votes));
+    })
+}
+
+#[test]
+fn only_relayers_take_part_in_dorr() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id as u8, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, remark_resource()).execute_with(|| {
+        assert!(<Bridge as Contains<u64>>::contains(&RELAYER_A));
+        assert!(!<Bridge as Contains<u64>>::contains(&USER));
+        assert_eq!(
+            <Bridge as Contains<u64>>::sorted_members(),
+            vec![RELAYER_A, RELAYER_B, RELAYER_C]
+        );
+
+        // Accounts that aren't relayers can't register a key
+        let keypair = relayer_keypair(USER);
+        let payload = Dorr::pk_ownership_payload(&USER, Dorr::get_current_epoch());
+        let signature = sr25519::Signature::from_raw(keypair.sign_simple(b"substrate", &payload).to_bytes());
+        assert_noop!(
+            Dorr::set_pk(Origin::signed(USER), keypair.public.to_bytes().to_vec(), signature),
+            dorr::Error::<Test>::NotEligible
+        );
+
+        // Removing a relayer removes its key and VRF result
+        activate_relayers();
+        let pk = relayer_keypair(RELAYER_C).public.to_bytes().to_vec();
+        assert!(Dorr::is_active(pk.clone()));
+        assert_ok!(Bridge::remove_relayer(Origin::root(), RELAYER_C));
+        assert!(!Dorr::is_active(pk.clone()));
+        assert!(Dorr::vrf_results(RELAYER_C) == Default::default());
+        assert_eq!(Dorr::pk_owner(pk), None);
+
+        let mut voters = Bridge::eligible_voters();
+        voters.sort();
+        assert_eq!(voters, vec![RELAYER_A, RELAYER_B]);
+    })
+}
+
+// Advances to block `n`, running the bridge's `on_initialize` hook at the start of every block.
+fn run_to_block_with_pruning(n: u64) {
+    while System::block_number() < n {
+        run_to_block(System::block_number() + 1);
+        Bridge::on_initialize(System::block_number());
+    }
+}
+
+#[test]
+fn expired_proposal_is_pruned() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id as u8, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, remark_resource()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+        let prop_hash = proposal_hash(&proposal);
+
+        activate_relayers();
+        let created_at = System::block_number();
+        let expiry = created_at + ProposalLifetime::get();
+
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            ProposalOrHash::Proposal(Box::new(proposal.clone()))
+        ));
+        assert_eq!(Bridge::prune_queue(expiry), vec![(src_id, prop_id, prop_hash)]);
+
+        run_to_block_with_pruning(expiry - 1);
+        assert!(Bridge::votes((src_id, prop_id), prop_hash).is_some());
+
+        run_to_block_with_pruning(expiry);
+        assert_eq!(Bridge::votes((src_id, prop_id), prop_hash), None);
+        assert_eq!(Bridge::proposal_preimage((src_id, prop_id), prop_hash), None);
+        assert_eq!(Bridge::prune_queue(expiry), vec![]);
+
+        assert_events(vec![Event::bridge(RawEvent::ProposalPruned(src_id, prop_id))]);
+    })
+}
+
+#[test]
+fn resolved_proposal_is_pruned_after_retention() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id as u8, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, remark_resource()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+        let prop_hash = proposal_hash(&proposal);
+
+        activate_relayers();
+        let created_at = System::block_number();
+        let expiry = created_at + ProposalLifetime::get();
+
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            ProposalOrHash::Proposal(Box::new(proposal.clone()))
+        ));
+
+        run_to_block_with_pruning(created_at + 5);
+        let resolved_at = System::block_number();
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_B),
+            prop_id,
+            src_id,
+            r_id,
+            ProposalOrHash::Proposal(Box::new(proposal.clone()))
+        ));
+
+        // The proposal is now only queued for the end of its retention period
+        let retained_until = resolved_at + ProposalRetention::get();
+        assert_eq!(Bridge::prune_queue(expiry), vec![]);
+        assert_eq!(Bridge::prune_queue(retained_until), vec![(src_id, prop_id, prop_hash)]);
+
+        run_to_block_with_pruning(retained_until - 1);
+        assert!(Bridge::votes((src_id, prop_id), prop_hash).is_some());
+
+        run_to_block_with_pruning(retained_until);
+        assert_eq!(Bridge::votes((src_id, prop_id), prop_hash), None);
+
+        assert_events(vec![Event::bridge(RawEvent::ProposalPruned(src_id, prop_id))]);
+    })
+}
+
+#[test]
+fn pruning_is_bounded_per_block() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id as u8, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, remark_resource()).execute_with(|| {
+        let proposal = make_proposal(vec![10]);
+        let prop_hash = proposal_hash(&proposal);
+
+        activate_relayers();
+        let expiry = System::block_number() + ProposalLifetime::get();
+
+        for prop_id in 1..4 {
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_A),
+                prop_id,
+                src_id,
+                r_id,
+                ProposalOrHash::Proposal(Box::new(proposal.clone()))
+            ));
+        }
+
+        // Only two proposals fit into a single block's budget
+        run_to_block_with_pruning(expiry);
+        assert_eq!(Bridge::votes((src_id, 1), prop_hash), None);
+        assert_eq!(Bridge::votes((src_id, 2), prop_hash), None);
+        assert!(Bridge::votes((src_id, 3), prop_hash).is_some());
+        assert_eq!(Bridge::prune_cursor(), expiry);
+
+        // The remaining proposal is picked up by the next block
+        run_to_block_with_pruning(expiry + 1);
+        assert_eq!(Bridge::votes((src_id, 3), prop_hash), None);
+        assert_eq!(Bridge::prune_queue(expiry), vec![]);
+
+        assert_events(vec![
+            Event::bridge(RawEvent::ProposalPruned(src_id, 1)),
+            Event::bridge(RawEvent::ProposalPruned(src_id, 2)),
+            Event::bridge(RawEvent::ProposalPruned(src_id, 3)),
+        ]);
     })
 }
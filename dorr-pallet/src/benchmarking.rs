@@ -0,0 +1,154 @@
+//! Benchmarks for the DORR pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::OnInitialize;
+use frame_system::RawOrigin;
+use rustc_hex::FromHex;
+use sp_core::crypto::KeyTypeId;
+use sp_std::vec;
+
+/// Key type of the keys generated in the keystore for the benchmarks
+const KEY_TYPE: KeyTypeId = KeyTypeId(*b"dorr");
+
+const SEED: u32 = 0;
+/// Upper bound of the number of stored VRF results in the benchmarks
+const MAX_RESULTS: u32 = 1000;
+
+// A VRF output and proof of the `//Alice` development key over the default randomness. Proofs
+// can't be generated inside the runtime, so the verification is benchmarked with this fixed vector.
+const PK_SEED: &str = "//Alice";
+const VRF_VAL: &str = "dcd0f3a7d0af4a1336b7cad05ffedd3486ca88c6e32eb096b301dced2ae43f5a";
+const VRF_PROOF: &str = "fa44dbfe6f3d4b49b623777b28412fac2168a463360ef0b531fdb70a76643b07910a8a616f00861a6399d70477918d5cf04e18a7fe298779eae862003027f302";
+
+fn decode_hex(hex: &str) -> Result<Vec<u8>, &'static str> {
+	hex.from_hex().map_err(|_| "invalid hex")
+}
+
+// Generates a key in the keystore, from `seed` if given, and signs the proof that `who` possesses
+// it in the current epoch
+fn generate_pk<T: Trait>(
+	who: &T::AccountId,
+	seed: Option<&str>,
+) -> Result<(Vec<u8>, sr25519::Signature), &'static str> {
+	let public = sp_io::crypto::sr25519_generate(KEY_TYPE, seed.map(|s| s.as_bytes().to_vec()));
+	let payload = Module::<T>::pk_ownership_payload(who, Module::<T>::get_current_epoch());
+	let signature = sp_io::crypto::sr25519_sign(KEY_TYPE, &public, &payload).ok_or("failed to sign")?;
+	Ok((public.0.to_vec(), signature))
+}
+
+benchmarks! {
+	_ { }
+
+	// Worst case: an active caller replaces a previously registered key
+	set_pk {
+		let caller: T::AccountId = whitelisted_caller();
+		T::EligibleCandidates::add(&caller);
+		let (previous_pk, signature) = generate_pk::<T>(&caller, None)?;
+		Module::<T>::set_pk(RawOrigin::Signed(caller.clone()).into(), previous_pk.clone(), signature)?;
+		Module::<T>::insert_vrf_result_unchecked(&caller, previous_pk.clone(), decode_hex(VRF_VAL)?);
+		let (pk, signature) = generate_pk::<T>(&caller, None)?;
+	}: _(RawOrigin::Signed(caller.clone()), pk.clone(), signature)
+	verify {
+		assert_eq!(<VrfResults<T>>::get(&caller).pk, pk);
+		assert_eq!(<PkOwners<T>>::get(&pk), Some(caller.clone()));
+		assert!(!<PkOwners<T>>::contains_key(&previous_pk));
+		assert!(!Module::<T>::is_active_relayer(&caller));
+	}
+
+	// Worst case: the caller is active
+	purge_pk {
+		let caller: T::AccountId = whitelisted_caller();
+		T::EligibleCandidates::add(&caller);
+		let (pk, signature) = generate_pk::<T>(&caller, None)?;
+		Module::<T>::set_pk(RawOrigin::Signed(caller.clone()).into(), pk.clone(), signature)?;
+		Module::<T>::insert_vrf_result_unchecked(&caller, pk.clone(), decode_hex(VRF_VAL)?);
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(<VrfResults<T>>::get(&caller).pk.is_empty());
+		assert!(!<PkToEpoch<T>>::contains_key(&pk));
+		assert!(!<PkOwners<T>>::contains_key(&pk));
+		assert!(!Module::<T>::is_active_relayer(&caller));
+	}
+
+	set_vrf_results {
+		let caller: T::AccountId = whitelisted_caller();
+		T::EligibleCandidates::add(&caller);
+		frame_system::Module::<T>::set_block_number(1u32.into());
+		let (pk, signature) = generate_pk::<T>(&caller, Some(PK_SEED))?;
+		Module::<T>::set_pk(RawOrigin::Signed(caller.clone()).into(), pk, signature)?;
+		// Results can only be submitted in an epoch after the key was set
+		let next_epoch_block = T::EpochDuration::get() as u32 + 1;
+		frame_system::Module::<T>::set_block_number(next_epoch_block.into());
+		let val = decode_hex(VRF_VAL)?;
+		let proof = decode_hex(VRF_PROOF)?;
+	}: _(RawOrigin::Signed(caller.clone()), val.clone(), proof)
+	verify {
+		assert_eq!(<VrfResults<T>>::get(&caller).val, val);
+		assert_eq!(<VrfResults<T>>::get(&caller).epoch, Module::<T>::next_election_epoch());
+	}
+
+	// First block of an epoch with `r` stored results, every one of which has either expired or
+	// takes part in the election
+	new_epoch {
+		let r in 1 .. MAX_RESULTS;
+
+		let lifetime = T::VrfResultLifetime::get().max(1);
+		let epoch = lifetime + 2;
+		let block = (epoch - 1) * T::EpochDuration::get() as u32 + 1;
+		frame_system::Module::<T>::set_block_number(block.into());
+		for i in 0..r {
+			let relayer: T::AccountId = account("relayer", i, SEED);
+			let result_epoch = if i % 2 == 0 { epoch } else { epoch - lifetime };
+			<VrfResults<T>>::insert(&relayer, VrfResult {
+				pk: i.encode(),
+				val: (i + 1).to_be_bytes().to_vec(),
+				proof: vec![1; 64],
+				epoch: result_epoch.into(),
+			});
+		}
+	}: { <Module<T> as OnInitialize<T::BlockNumber>>::on_initialize(block.into()); }
+	verify {
+		let candidates = (r + 1) / 2;
+		let elected = candidates.min(T::MaxActiveRelayers::get() as u32);
+		assert_eq!(Module::<T>::active_relayers().len() as u32, elected);
+		assert!(<EpochToRandomness<T>>::contains_key(T::BlockNumber::from(epoch)));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn set_pk() {
+		new_test_ext(1).execute_with(|| {
+			assert_ok!(test_benchmark_set_pk::<Test>());
+		});
+	}
+
+	#[test]
+	fn purge_pk() {
+		new_test_ext(1).execute_with(|| {
+			assert_ok!(test_benchmark_purge_pk::<Test>());
+		});
+	}
+
+	#[test]
+	fn set_vrf_results() {
+		new_test_ext(1).execute_with(|| {
+			assert_ok!(test_benchmark_set_vrf_results::<Test>());
+		});
+	}
+
+	#[test]
+	fn new_epoch() {
+		new_test_ext(1).execute_with(|| {
+			assert_ok!(test_benchmark_new_epoch::<Test>());
+		});
+	}
+}
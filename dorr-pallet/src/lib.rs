@@ -2,14 +2,26 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::{Encode, Decode};
-use frame_support::{weights::Weight, decl_event, decl_module, decl_storage, decl_error, ensure, dispatch::DispatchResult, traits::{ Randomness, Get }};
+use frame_support::{weights::Weight, decl_event, decl_module, decl_storage, decl_error, ensure, dispatch::DispatchResult, traits::{ Contains, Randomness, Get }};
 use frame_system::{ensure_signed};
-use sp_std::prelude::*;
-use sp_core;
-
+use sp_runtime::{RuntimeDebug, traits::{Saturating, Zero}};
+use sp_std::{cmp::Reverse, prelude::*};
+use sp_core::{self, sr25519};
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+mod default_weights;
+mod migrations;
 mod mock;
 mod tests;
 
+pub trait WeightInfo {
+	fn set_pk() -> Weight;
+	fn purge_pk() -> Weight;
+	fn set_vrf_results() -> Weight;
+	fn new_epoch(r: u32) -> Weight;
+}
+
 // Configuration
 pub trait Trait: frame_system::Trait {
 	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
@@ -20,22 +32,57 @@ pub trait Trait: frame_system::Trait {
 	/// Maximum amount relayers in active
 	type MaxActiveRelayers: Get<u8>;
 
+	/// Amount of epochs after its election epoch a VRF result is cleared in. At least 1.
+	/// Results only take part in the election of their epoch, the lifetime only bounds how long
+	/// they're kept in storage.
+	type VrfResultLifetime: Get<u32>;
+
 	type RandomnessSource: Randomness<<Self as frame_system::Trait>::Hash>;
+
+	/// Accounts allowed to register keys and take part in the election. The provider should call
+	/// `remove_candidate` when an account stops being eligible.
+	type EligibleCandidates: Contains<Self::AccountId>;
+
+	/// Weight information for extrinsics in this pallet
+	type WeightInfo: WeightInfo;
+}
+
+/// Prefix of the payloads signed to prove possession of a public key
+const PK_OWNERSHIP_CONTEXT: &[u8] = b"dorr/pk-ownership";
+
+/// Storage layout versions of the pallet
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, RuntimeDebug)]
+enum Releases {
+	/// Public keys aren't bound to an account
+	V1UnboundKeys,
+	/// Public keys are owned by the account that registered them
+	V2OwnedKeys,
+	/// VRF results are bound to the epoch they're elected for
+	V3EpochBoundResults,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V1UnboundKeys
+	}
 }
 
 #[derive(PartialEq, Eq, Clone, Encode, Decode)]
-pub struct VrfResult {
+pub struct VrfResult<BlockNumber> {
 	pk: Vec<u8>,
 	val: Vec<u8>,
 	proof: Vec<u8>,
+	/// Epoch the result takes part in the election of
+	epoch: BlockNumber,
 }
 
-impl Default for VrfResult {
+impl<BlockNumber: Default> Default for VrfResult<BlockNumber> {
 	fn default() -> Self {
 		VrfResult {
 			pk: Default::default(),
 			val: Default::default(),
 			proof: Default::default(),
+			epoch: Default::default(),
 		}
 	}
 }
@@ -43,19 +90,31 @@ impl Default for VrfResult {
 // Storage
 decl_storage! {
 	trait Store for Module<T: Trait> as DorrStorage {
-		VrfResults get(fn vrf_results): map hasher(blake2_128_concat) T::AccountId => VrfResult;
+		VrfResults get(fn vrf_results): map hasher(blake2_128_concat) T::AccountId => VrfResult<T::BlockNumber>;
 		PkToBlockNumber get(fn pk_to_block_number): map hasher(blake2_128_concat) Vec<u8> => <T as frame_system::Trait>::BlockNumber;
 		PkToEpoch get(fn pk_to_epoch): map hasher(blake2_128_concat) Vec<u8> => <T as frame_system::Trait>::BlockNumber;
 		EpochToRandomness get(fn epoch_to_randomness): map hasher(blake2_128_concat) <T as frame_system::Trait>::BlockNumber => <T as frame_system::Trait>::Hash;
+		/// Account that registered each public key. A key can only be registered by one account.
+		PkOwners get(fn pk_owner): map hasher(blake2_128_concat) Vec<u8> => Option<T::AccountId>;
+		/// Relayers elected for the current epoch, sorted by account. Holds at most
+		/// `MaxActiveRelayers` accounts.
+		ActiveRelayers get(fn active_relayers): Vec<T::AccountId>;
+
+		/// Storage layout version, used to run storage migrations. New chains start with the
+		/// latest layout.
+		StorageVersion build(|_: &GenesisConfig| Releases::V3EpochBoundResults): Releases;
 	}
 }
 
 // Events
 decl_event! {
 	pub enum Event<T> where
+			<T as frame_system::Trait>::AccountId,
 			<T as frame_system::Trait>::BlockNumber,
 	{
 			NewPKSetted(Vec<u8>, BlockNumber, BlockNumber),
+			/// The active relayers of an epoch changed. [epoch, relayers]
+			ActiveSetChanged(BlockNumber, Vec<AccountId>),
 	}
 }
 
@@ -65,6 +124,18 @@ decl_error! {
 		PkIsNotSetted,
 		BadVrfProof,
 		InvalidEpochTooEarly,
+		/// The public key is not an encoded sr25519 public key
+		InvalidPublicKey,
+		/// The VRF output is not an encoded VRF pre-output
+		MalformedVrfOutput,
+		/// The VRF proof is not an encoded VRF proof
+		MalformedVrfProof,
+		/// The signature doesn't prove possession of the public key by the account
+		InvalidPkSignature,
+		/// The public key is registered by another account
+		PkAlreadyInUse,
+		/// The account is not allowed to take part in the election
+		NotEligible,
 	}
 }
 
@@ -86,24 +157,66 @@ decl_module! {
 
 		const MaxActiveRelayers: u8 = T::MaxActiveRelayers::get();
 		const EpochDuration: u8 = T::EpochDuration::get();
+		const VrfResultLifetime: u32 = T::VrfResultLifetime::get();
 
+		fn on_runtime_upgrade() -> Weight {
+			migrations::migrate::<T>()
+		}
+
+		/// Starts a new epoch on its first block. The randomness of the epoch is fixed, the
+		/// expired VRF results are cleared and the active relayers of the epoch are elected.
+		///
+		/// # <weight>
+		/// - O(R) in the number of stored VRF results on the first block of an epoch
+		/// # </weight>
 		fn on_initialize(block_number: T::BlockNumber) -> Weight {
-			<EpochToRandomness<T>>::insert(Self::get_current_epoch(), T::RandomnessSource::random_seed());
-			0
+			let epoch = Self::get_current_epoch();
+			if <EpochToRandomness<T>>::contains_key(epoch) {
+				return T::DbWeight::get().reads(1);
+			}
+			<EpochToRandomness<T>>::insert(epoch, T::RandomnessSource::random_seed());
+			let results = Self::clear_expired_results(epoch);
+			Self::elect_active_relayers(epoch);
+			T::WeightInfo::new_epoch(results)
 		}
 
-		#[weight = 195_000_000]
-		pub fn set_pk(origin, pk: Vec<u8>) -> DispatchResult {
+		/// Registers the public key of the relayer.
+		///
+		/// `signature` must be made by the key over `pk_ownership_payload` of the relayer and the
+		/// current epoch, proving that the relayer possesses the key.
+		#[weight = T::WeightInfo::set_pk()]
+		pub fn set_pk(origin, pk: Vec<u8>, signature: sr25519::Signature) -> DispatchResult {
 			let relayer = ensure_signed(origin)?;
+			ensure!(T::EligibleCandidates::contains(&relayer), Error::<T>::NotEligible);
+			let public_key = Self::decode_public_key(&pk)?;
+			let current_epoch = Self::get_current_epoch();
+			let payload = Self::pk_ownership_payload(&relayer, current_epoch);
+			ensure!(
+				sp_io::crypto::sr25519_verify(&signature, &payload, &sr25519::Public::from_raw(public_key.to_bytes())),
+				Error::<T>::InvalidPkSignature
+			);
+			if let Some(owner) = Self::pk_owner(&pk) {
+				ensure!(owner == relayer, Error::<T>::PkAlreadyInUse);
+			}
 			let current_block = <frame_system::Module<T>>::block_number();
 
-			<PkToEpoch<T>>::insert(pk.clone(), Self::get_current_epoch());
+			// A relayer has a single key, the previous one becomes available again
+			let previous_pk = <VrfResults<T>>::get(&relayer).pk;
+			if !previous_pk.is_empty() && previous_pk != pk {
+				Self::remove_pk(&previous_pk);
+			}
+			// The result the relayer was elected with is cleared
+			Self::deactivate(&relayer);
+
+			<PkToEpoch<T>>::insert(pk.clone(), current_epoch);
 			<PkToBlockNumber<T>>::insert(pk.clone(), current_block);
+			<PkOwners<T>>::insert(pk.clone(), &relayer);
 			
 			<VrfResults<T>>::insert(&relayer, VrfResult {
 				pk: pk.clone(),
 				val: Vec::new(),
 				proof: Vec::new(),
+				epoch: Zero::zero(),
 			});
 
 			Self::deposit_event(RawEvent::NewPKSetted(pk.clone(), <PkToBlockNumber<T>>::get(&pk), <PkToEpoch<T>>::get(&pk)));
@@ -111,42 +224,48 @@ decl_module! {
 			Ok(())
 		}
 
-		#[weight = 195_000_000]
+		#[weight = T::WeightInfo::purge_pk()]
 		pub fn purge_pk(origin) -> DispatchResult {
 			let relayer = ensure_signed(origin)?;
 			let result = <VrfResults<T>>::get(&relayer);
-			let pk = result.pk;
-
-			<PkToBlockNumber<T>>::remove(&pk);
-			<PkToEpoch<T>>::remove(&pk);
+			Self::remove_pk(&result.pk);
+			Self::deactivate(&relayer);
 			
-			<VrfResults<T>>::insert(&relayer, VrfResult {
-				pk: Vec::new(),
-				val: Vec::new(),
-				proof: Vec::new(),
-			});
+			<VrfResults<T>>::insert(&relayer, VrfResult::default());
 
 			Ok(())
 		}
 
-		#[weight = 195_000_000]
+		/// Submits the VRF result of the relayer for the election of the next epoch.
+		///
+		/// Results are submitted during epoch `e` over the randomness of `e`, which is fixed on the
+		/// first block of `e`, with a key registered before `e`. The active relayers of `e + 1` are
+		/// the relayers with the highest results submitted during `e`, and the set is final once
+		/// `e + 1` starts.
+		#[weight = T::WeightInfo::set_vrf_results()]
 		pub fn set_vrf_results(origin, val: Vec<u8>, proof: Vec<u8>) -> DispatchResult {
 			let relayer = ensure_signed(origin)?;
+			ensure!(T::EligibleCandidates::contains(&relayer), Error::<T>::NotEligible);
 
 			ensure!(<VrfResults<T>>::contains_key(&relayer), Error::<T>::PkIsNotSetted);
 
 			let result = <VrfResults<T>>::get(&relayer);
 			let current_epoch = Self::get_current_epoch();
 			let epoch = Self::get_epoch_by_pk(result.pk.clone());
-			let public_randomness = Self::get_public_randomness(epoch);
+			let public_randomness = Self::get_public_randomness(current_epoch);
 
 			ensure!(epoch < current_epoch, Error::<T>::InvalidEpochTooEarly);
 
-			let vrf_output = schnorrkel::vrf::VRFPreOut::from_bytes(&val).unwrap();
-			let vrf_proof = schnorrkel::vrf::VRFProof::from_bytes(&proof).unwrap();
-			let verified = schnorrkel::PublicKey::from_bytes(&result.pk).and_then(|p| {
-				p.vrf_verify(schnorrkel::signing_context(&Vec::<u8>::new()).bytes(public_randomness.as_ref()), &vrf_output, &vrf_proof)
-			});
+			let public_key = Self::decode_public_key(&result.pk)?;
+			let vrf_output = schnorrkel::vrf::VRFPreOut::from_bytes(&val)
+				.map_err(|_| Error::<T>::MalformedVrfOutput)?;
+			let vrf_proof = schnorrkel::vrf::VRFProof::from_bytes(&proof)
+				.map_err(|_| Error::<T>::MalformedVrfProof)?;
+			let verified = public_key.vrf_verify(
+				schnorrkel::signing_context(&Vec::<u8>::new()).bytes(public_randomness.as_ref()),
+				&vrf_output,
+				&vrf_proof,
+			);
 
 			ensure!(verified.is_ok(), Error::<T>::BadVrfProof);
 
@@ -154,6 +273,7 @@ decl_module! {
 				pk: result.pk,
 				val: val,
 				proof: proof,
+				epoch: Self::next_election_epoch(),
 			});
 
 			Ok(())
@@ -162,6 +282,36 @@ decl_module! {
 }
 
 impl<T: Trait> Module<T> {
+	/// Payload to sign with a public key to register it for `relayer` in `epoch`
+	pub fn pk_ownership_payload(relayer: &T::AccountId, epoch: T::BlockNumber) -> Vec<u8> {
+		(PK_OWNERSHIP_CONTEXT, relayer, epoch).encode()
+	}
+
+	/// Removes the key and VRF result of an account that is no longer eligible, and removes it from
+	/// the active relayers
+	pub fn remove_candidate(who: &T::AccountId) {
+		let result = <VrfResults<T>>::take(who);
+		Self::remove_pk(&result.pk);
+		Self::deactivate(who);
+	}
+
+	/// Removes the relayer from the active relayers of the current epoch
+	fn deactivate(who: &T::AccountId) {
+		let mut active = Self::active_relayers();
+		if let Ok(index) = active.binary_search(who) {
+			active.remove(index);
+			<ActiveRelayers<T>>::put(&active);
+			Self::deposit_event(RawEvent::ActiveSetChanged(Self::get_current_epoch(), active));
+		}
+	}
+
+	/// Removes the registration of a public key
+	fn remove_pk(pk: &[u8]) {
+		<PkToBlockNumber<T>>::remove(pk);
+		<PkToEpoch<T>>::remove(pk);
+		<PkOwners<T>>::remove(pk);
+	}
+
 	pub fn get_epoch_by_pk(pk: Vec<u8>) -> T::BlockNumber {
 		return <PkToEpoch<T>>::get(pk);
 	}
@@ -177,13 +327,69 @@ impl<T: Trait> Module<T> {
 		return <EpochToRandomness<T>>::get(epoch);
 	}
 
+	/// Epoch the VRF results submitted in the current epoch are elected for
+	pub fn next_election_epoch() -> T::BlockNumber {
+		Self::get_current_epoch() + 1u32.into()
+	}
+
+	/// Clears the VRF results that have expired by the start of `epoch`, and the randomness they
+	/// were submitted over. Returns the number of stored results.
+	fn clear_expired_results(epoch: T::BlockNumber) -> u32 {
+		let lifetime = T::BlockNumber::from(T::VrfResultLifetime::get().max(1));
+		let mut results = 0u32;
+		let expired: Vec<_> = <VrfResults<T>>::iter()
+			.filter(|(_, result)| {
+				results += 1;
+				!result.val.is_empty() && result.epoch.saturating_add(lifetime) <= epoch
+			})
+			.map(|(account_id, _)| account_id)
+			.collect();
+		for account_id in expired.iter() {
+			<VrfResults<T>>::mutate(account_id, |result| {
+				result.val = Vec::new();
+				result.proof = Vec::new();
+			});
+		}
+
+		// Results elected for `epoch - lifetime` were submitted over the randomness of the epoch before
+		let randomness_epoch = epoch.saturating_sub(lifetime).saturating_sub(1u32.into());
+		if !randomness_epoch.is_zero() {
+			<EpochToRandomness<T>>::remove(randomness_epoch);
+		}
+		results
+	}
+
+	/// Decodes an sr25519 public key, rejecting keys of the wrong length and invalid points
+	fn decode_public_key(pk: &[u8]) -> Result<schnorrkel::PublicKey, Error<T>> {
+		schnorrkel::PublicKey::from_bytes(pk).map_err(|_| Error::<T>::InvalidPublicKey)
+	}
+
 	fn vrf_val_to_int(val: &[u8]) -> sp_core::U256 {
 		return sp_core::U256::from_big_endian(val);
 	}
 
-	pub fn sorted_active_relayers() -> Vec<T::AccountId> {
+	/// Elects the active relayers of `epoch`, the relayers with the highest VRF results submitted
+	/// for it. Returns the weight consumed.
+	fn elect_active_relayers(epoch: T::BlockNumber) -> Weight {
+		let (mut elected, reads) = Self::elected_relayers(epoch);
+		elected.sort();
+		if elected == Self::active_relayers() {
+			return T::DbWeight::get().reads(reads + 1);
+		}
+		<ActiveRelayers<T>>::put(&elected);
+		Self::deposit_event(RawEvent::ActiveSetChanged(epoch, elected));
+		T::DbWeight::get().reads_writes(reads + 1, 1)
+	}
+
+	/// Relayers with the highest VRF results for `epoch`, by decreasing VRF output, and the
+	/// number of results read
+	fn elected_relayers(epoch: T::BlockNumber) -> (Vec<T::AccountId>, Weight) {
 		let max_active_relayers = T::MaxActiveRelayers::get();
-		let mut results: Vec<_> = VrfResults::<T>::iter().collect();
+		let mut reads: Weight = 0;
+		let mut results: Vec<_> = VrfResults::<T>::iter()
+			.inspect(|_| reads += 1)
+			.filter(|(_, result)| result.epoch == epoch)
+			.collect();
 
 		results.sort_by_key(|a| Reverse(Self::vrf_val_to_int(a.1.val.as_slice())));
 
@@ -199,7 +405,17 @@ impl<T: Trait> Module<T> {
 			sorted.push(account_id.clone());
 		}
 
-		return sorted;
+		return (sorted, reads);
+	}
+
+	/// Relayers elected for the current epoch, sorted by account
+	pub fn sorted_active_relayers() -> Vec<T::AccountId> {
+		Self::active_relayers()
+	}
+
+	/// Returns true if the relayer is elected for the current epoch
+	pub fn is_active_relayer(who: &T::AccountId) -> bool {
+		Self::active_relayers().binary_search(who).is_ok()
 	}
 
 	pub fn get_active_pks() -> Vec<Vec<u8>> {
@@ -217,6 +433,33 @@ impl<T: Trait> Module<T> {
 	}
 
 	pub fn is_active(relayer_pk: Vec<u8>) -> bool {
-		Self::get_active_pks().binary_search(&relayer_pk).is_ok()
+		match Self::pk_owner(&relayer_pk) {
+			Some(owner) => Self::is_active_relayer(&owner),
+			None => false,
+		}
+	}
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl<T: Trait> Module<T> {
+	/// Stores a VRF result for the relayer without verifying it, and adds the relayer to the active
+	/// relayers of the current epoch while there is room.
+	/// Allows benchmarks of dependent pallets to set up an active relayer set, as proofs can't be
+	/// generated inside the runtime.
+	pub fn insert_vrf_result_unchecked(relayer: &T::AccountId, pk: Vec<u8>, val: Vec<u8>) {
+		<PkOwners<T>>::insert(&pk, relayer);
+		<VrfResults<T>>::insert(relayer, VrfResult {
+			pk,
+			val,
+			proof: Vec::new(),
+			epoch: Self::get_current_epoch(),
+		});
+		<ActiveRelayers<T>>::mutate(|active| {
+			if let Err(index) = active.binary_search(relayer) {
+				if active.len() < T::MaxActiveRelayers::get() as usize {
+					active.insert(index, relayer.clone());
+				}
+			}
+		});
 	}
 }
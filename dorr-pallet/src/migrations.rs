@@ -0,0 +1,123 @@
+//! Storage migrations for the DORR pallet.
+//!
+//! Migrations are run from `on_runtime_upgrade` and tracked by `StorageVersion`, so each of
+//! them is applied once.
+
+use super::*;
+use frame_support::storage::{IterableStorageMap, StorageMap, StorageValue};
+use sp_std::collections::btree_map::BTreeMap;
+
+/// Applies the migrations the storage hasn't been migrated with yet.
+pub fn migrate<T: Trait>() -> Weight {
+	let mut weight = T::DbWeight::get().reads(1);
+	let version = StorageVersion::get();
+	// The results are translated first, the other steps read them in the current layout
+	let mut dropped_keys = Vec::new();
+	if version < Releases::V3EpochBoundResults {
+		let (results_weight, keys) = bind_results_to_epochs::<T>();
+		weight = weight.saturating_add(results_weight);
+		dropped_keys = keys;
+	}
+	if version < Releases::V2OwnedKeys {
+		weight = weight.saturating_add(bind_keys_to_owners::<T>());
+	}
+	if version < Releases::V3EpochBoundResults {
+		weight = weight.saturating_add(drop_ineligible_keys::<T>(dropped_keys));
+		// The active relayers are elected on the first block of an epoch, so the relayers of the
+		// epoch the upgrade happens in are elected now
+		let epoch = <Module<T>>::get_current_epoch();
+		weight = weight.saturating_add(<Module<T>>::elect_active_relayers(epoch));
+		StorageVersion::put(Releases::V3EpochBoundResults);
+		weight = weight.saturating_add(T::DbWeight::get().writes(1));
+	}
+	weight
+}
+
+/// `VrfResult` without an election epoch
+#[derive(Decode)]
+struct OldVrfResult {
+	pk: Vec<u8>,
+	val: Vec<u8>,
+	proof: Vec<u8>,
+}
+
+/// Binds the stored VRF results to an election epoch. The relayers were elected from the stored
+/// results until now, so they're bound to the current epoch and keep the bridge running until
+/// the relayers submit results for the next epoch.
+///
+/// The results of accounts that are no longer in `EligibleCandidates` are dropped instead, so
+/// that they aren't elected by the upgrade. Returns the keys of the dropped results.
+fn bind_results_to_epochs<T: Trait>() -> (Weight, Vec<Vec<u8>>) {
+	let epoch = <Module<T>>::get_current_epoch();
+	let translated = sp_std::cell::Cell::new(0u64);
+	let dropped = sp_std::cell::RefCell::new(Vec::new());
+	<VrfResults<T>>::translate(|who, result: OldVrfResult| {
+		translated.set(translated.get() + 1);
+		if !T::EligibleCandidates::contains(&who) {
+			dropped.borrow_mut().push(result.pk);
+			return None;
+		}
+		Some(VrfResult {
+			pk: result.pk,
+			val: result.val,
+			proof: result.proof,
+			epoch,
+		})
+	});
+
+	let translated = translated.get();
+	(T::DbWeight::get().reads_writes(translated, translated), dropped.into_inner())
+}
+
+/// Removes the registrations of keys owned by accounts that are no longer eligible, and of the
+/// keys of dropped results that no eligible account owns.
+fn drop_ineligible_keys<T: Trait>(dropped: Vec<Vec<u8>>) -> Weight {
+	let mut reads = 0u64;
+	let mut stale: Vec<Vec<u8>> = dropped
+		.into_iter()
+		.filter(|pk| {
+			reads += 1;
+			!pk.is_empty() && <PkOwners<T>>::get(pk).is_none()
+		})
+		.collect();
+	for (pk, owner) in <PkOwners<T>>::iter() {
+		reads += 1;
+		if !T::EligibleCandidates::contains(&owner) {
+			stale.push(pk);
+		}
+	}
+
+	for pk in &stale {
+		<Module<T>>::remove_pk(pk);
+	}
+	T::DbWeight::get().reads_writes(reads, stale.len() as u64 * 3)
+}
+
+/// Records the owners of the keys registered before keys were bound to an account. A key that
+/// was registered by several accounts can't be attributed to one of them, so it's removed from
+/// all of them, and they register a key again.
+fn bind_keys_to_owners<T: Trait>() -> Weight {
+	let mut claimants: BTreeMap<Vec<u8>, Vec<T::AccountId>> = BTreeMap::new();
+	let mut reads = 0u64;
+	for (who, result) in <VrfResults<T>>::iter() {
+		reads += 1;
+		if !result.pk.is_empty() {
+			claimants.entry(result.pk).or_default().push(who);
+		}
+	}
+
+	let mut writes = 0u64;
+	for (pk, accounts) in claimants {
+		if let [owner] = accounts.as_slice() {
+			<PkOwners<T>>::insert(&pk, owner);
+			writes += 1;
+		} else {
+			for who in &accounts {
+				<Module<T>>::remove_candidate(who);
+			}
+			reads += accounts.len() as u64;
+			writes += accounts.len() as u64 * 4;
+		}
+	}
+	T::DbWeight::get().reads_writes(reads, writes)
+}
@@ -2,9 +2,9 @@
 
 use super::*;
 
-use frame_support::{ord_parameter_types, parameter_types, weights::Weight};
+use frame_support::{dispatch::DispatchResult, ord_parameter_types, parameter_types, traits::Contains, weights::Weight};
 use frame_system::{self as system};
-use sp_core::H256;
+use sp_core::{sr25519, testing::KeyStore, traits::KeystoreExt, H256};
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, Block as BlockT, IdentityLookup},
@@ -14,6 +14,7 @@ use sp_runtime::{
 use crate::{self as dorr, Trait};
 use crate::sp_api_hidden_includes_decl_storage::hidden_include::traits::OnInitialize;
 use crate::sp_api_hidden_includes_decl_storage::hidden_include::traits::OnFinalize;
+use std::cell::RefCell;
 
 pub const ALICE: u64 = 0x1;
 pub const BOB: u64 = 0x2;
@@ -22,6 +23,7 @@ pub const CHARLIE: u64 = 0x3;
 parameter_types! {
 	pub const MaxActiveRelayers: u8 = 2;
 	pub const EpochDuration: u8 = 5;
+	pub const VrfResultLifetime: u32 = 2;
 	pub const BlockHashCount: u64 = 250;
 	pub const MaximumBlockWeight: Weight = 1024;
 	pub const MaximumBlockLength: u32 = 2 * 1024;
@@ -72,11 +74,39 @@ parameter_types! {
 	pub const ProposalLifetime: u64 = 100;
 }
 
+thread_local! {
+	static CANDIDATES: RefCell<Vec<u64>> = RefCell::new(Vec::new());
+}
+
+// Accounts eligible for the election, ALICE, BOB and CHARLIE unless a test sets them
+pub struct Candidates;
+impl Candidates {
+	pub fn set(mut candidates: Vec<u64>) {
+		candidates.sort();
+		CANDIDATES.with(|v| *v.borrow_mut() = candidates);
+	}
+}
+impl Contains<u64> for Candidates {
+	fn sorted_members() -> Vec<u64> {
+		CANDIDATES.with(|v| v.borrow().clone())
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn add(who: &u64) {
+		let mut candidates = Self::sorted_members();
+		candidates.push(*who);
+		Self::set(candidates);
+	}
+}
+
 impl Trait for Test {
 	type Event = Event;
 	type MaxActiveRelayers = MaxActiveRelayers;
 	type EpochDuration = EpochDuration;
+	type VrfResultLifetime = VrfResultLifetime;
 	type RandomnessSource = Randomness;
+	type EligibleCandidates = Candidates;
+	type WeightInfo = ();
 }
 
 pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
@@ -104,10 +134,43 @@ pub fn run_to_block(n: u64) {
 }
 
 pub fn new_test_ext(block_number: u64) -> sp_io::TestExternalities {
+	Candidates::set(vec![ALICE, BOB, CHARLIE]);
 	let t = frame_system::GenesisConfig::default()
 			.build_storage::<Test>()
 			.unwrap();
 	let mut ext = sp_io::TestExternalities::new(t);
+	// Used by the benchmarks to generate keys
+	ext.register_extension(KeystoreExt(KeyStore::new()));
 	ext.execute_with(|| System::set_block_number(block_number));
 	ext
 }
+
+// Deterministic sr25519 key pair of a test account
+pub fn keypair(who: u64) -> schnorrkel::Keypair {
+	schnorrkel::MiniSecretKey::from_bytes(&[who as u8; 32])
+		.unwrap()
+		.expand_to_keypair(schnorrkel::ExpansionMode::Ed25519)
+}
+
+pub fn public_key(who: u64) -> Vec<u8> {
+	keypair(who).public.to_bytes().to_vec()
+}
+
+// Signature by `signer` proving that `who` possesses its key in the current epoch
+pub fn pk_signature(signer: &schnorrkel::Keypair, who: u64) -> sr25519::Signature {
+	let payload = Dorr::pk_ownership_payload(&who, Dorr::get_current_epoch());
+	sr25519::Signature::from_raw(signer.sign_simple(b"substrate", &payload).to_bytes())
+}
+
+// Registers the key of `who`
+pub fn register_pk(who: u64) -> DispatchResult {
+	Dorr::set_pk(Origin::signed(who), public_key(who), pk_signature(&keypair(who), who))
+}
+
+// VRF output and proof of `who` over the randomness of the current epoch
+pub fn vrf_result(who: u64) -> (Vec<u8>, Vec<u8>) {
+	let randomness = Dorr::get_public_randomness(Dorr::get_current_epoch());
+	let context = schnorrkel::signing_context(&Vec::<u8>::new());
+	let (inout, proof, _) = keypair(who).vrf_sign(context.bytes(randomness.as_ref()));
+	(inout.to_preout().to_bytes().to_vec(), proof.to_bytes().to_vec())
+}
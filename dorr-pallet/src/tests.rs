@@ -1,9 +1,72 @@
 #![cfg(test)]
 
-extern crate rustc_hex;
-use frame_support::{assert_ok};
-use super::mock::{ALICE, BOB, CHARLIE, Origin, Dorr, new_test_ext, run_to_block};
-use rustc_hex::{FromHex};
+use codec::Encode;
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::DispatchResult,
+	storage::{unhashed, StorageMap, StorageValue},
+	traits::OnRuntimeUpgrade,
+};
+use super::mock::{
+	ALICE, BOB, CHARLIE, Candidates, Event, Origin, Dorr, System, Test, keypair, new_test_ext, pk_signature, public_key,
+	register_pk, run_to_block, vrf_result,
+};
+use super::{EpochToRandomness, Error, PkOwners, PkToEpoch, RawEvent, Releases, StorageVersion, VrfResults};
+use sp_core::{hashing::blake2_256, sr25519, H256, U256};
+
+// Deterministic pseudo-random bytes, so that failing inputs can be reproduced
+struct Fuzzer(u64);
+
+impl Fuzzer {
+	fn bytes(&mut self, len: usize) -> Vec<u8> {
+		let mut bytes = Vec::new();
+		while bytes.len() < len {
+			self.0 += 1;
+			bytes.extend_from_slice(&blake2_256(&self.0.encode()));
+		}
+		bytes.truncate(len);
+		bytes
+	}
+
+	fn below(&mut self, n: usize) -> usize {
+		let mut word = [0u8; 8];
+		word.copy_from_slice(&self.bytes(8));
+		(u64::from_le_bytes(word) % n as u64) as usize
+	}
+
+	// Bytes of the encoded length `len`, one byte shorter or longer, or of a random length
+	fn bytes_around(&mut self, len: usize) -> Vec<u8> {
+		let len = match self.below(4) {
+			0 => len - 1,
+			1 => len,
+			2 => len + 1,
+			_ => self.below(2 * len),
+		};
+		self.bytes(len)
+	}
+
+	fn signature(&mut self) -> sr25519::Signature {
+		let mut signature = [0u8; 64];
+		signature.copy_from_slice(&self.bytes(64));
+		sr25519::Signature::from_raw(signature)
+	}
+}
+
+fn assert_err_in(result: DispatchResult, errors: Vec<Error<Test>>) {
+	let err = result.expect_err("random input must be rejected");
+	assert!(errors.into_iter().any(|e| err == e.into()), "unexpected error {:?}", err);
+}
+
+// Active sets announced by `ActiveSetChanged` events
+fn active_set_changes() -> Vec<(u64, Vec<u64>)> {
+	System::events()
+		.into_iter()
+		.filter_map(|record| match record.event {
+			Event::dorr(RawEvent::ActiveSetChanged(epoch, relayers)) => Some((epoch, relayers)),
+			_ => None,
+		})
+		.collect()
+}
 
 #[test]
 fn current_epoch_calculating_should_work() {
@@ -18,41 +81,448 @@ fn current_epoch_calculating_should_work() {
 #[test]
 fn set_vrf_results_should_work() {
 	new_test_ext(1).execute_with(|| {
-		let pk_alice: String = String::from("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d");
-		assert_ok!(Dorr::set_pk(Origin::signed(ALICE), pk_alice.from_hex().unwrap()));
+		assert_ok!(register_pk(ALICE));
+		assert_ok!(register_pk(BOB));
+		assert_ok!(register_pk(CHARLIE));
+
+		run_to_block(6);
+		let mut outputs = Vec::new();
+		for relayer in [ALICE, BOB, CHARLIE].iter() {
+			let (val, proof) = vrf_result(*relayer);
+			outputs.push((U256::from_big_endian(&val), *relayer));
+			assert_ok!(Dorr::set_vrf_results(Origin::signed(*relayer), val, proof));
+		}
+		assert!(Dorr::sorted_active_relayers().is_empty());
+
+		// The two relayers with the highest outputs are active in the next epoch
+		run_to_block(11);
+		outputs.sort();
+		let (_, inactive) = outputs[0];
+		for relayer in [ALICE, BOB, CHARLIE].iter() {
+			assert_eq!(Dorr::is_active(public_key(*relayer)), *relayer != inactive);
+		}
+	});
+}
+
+#[test]
+fn results_are_elected_for_one_epoch() {
+	new_test_ext(1).execute_with(|| {
+		assert_ok!(register_pk(ALICE));
+		// Keys take part from the epoch after they're registered
+		let (val, proof) = vrf_result(ALICE);
+		assert_noop!(
+			Dorr::set_vrf_results(Origin::signed(ALICE), val, proof),
+			Error::<Test>::InvalidEpochTooEarly
+		);
+
+		run_to_block(6);
+		let (val, proof) = vrf_result(ALICE);
+		assert_ok!(Dorr::set_vrf_results(Origin::signed(ALICE), val, proof));
+		assert_eq!(Dorr::vrf_results(ALICE).epoch, 3);
+
+		run_to_block(11);
+		assert_eq!(Dorr::sorted_active_relayers(), vec![ALICE]);
+
+		// Without a new submission, the relayer is no longer active
+		run_to_block(16);
+		assert!(Dorr::sorted_active_relayers().is_empty());
+		assert!(!Dorr::vrf_results(ALICE).val.is_empty());
+
+		// A result submitted during epoch 4 is elected for epoch 5
+		let (val, proof) = vrf_result(ALICE);
+		assert_ok!(Dorr::set_vrf_results(Origin::signed(ALICE), val, proof));
+		assert_eq!(Dorr::vrf_results(ALICE).epoch, 5);
+		run_to_block(21);
+		assert_eq!(Dorr::sorted_active_relayers(), vec![ALICE]);
+	});
+}
+
+#[test]
+fn active_set_is_elected_once_per_epoch() {
+	new_test_ext(1).execute_with(|| {
+		assert_ok!(register_pk(ALICE));
+		assert_ok!(register_pk(BOB));
+		assert_ok!(register_pk(CHARLIE));
+		run_to_block(6);
+		let mut outputs = Vec::new();
+		for relayer in [ALICE, BOB, CHARLIE].iter() {
+			let (val, proof) = vrf_result(*relayer);
+			outputs.push((U256::from_big_endian(&val), *relayer));
+			assert_ok!(Dorr::set_vrf_results(Origin::signed(*relayer), val, proof));
+		}
+		assert!(Dorr::active_relayers().is_empty());
+
+		// The two relayers with the highest outputs, sorted by account
+		outputs.sort();
+		let (_, inactive) = outputs[0];
+		let mut elected: Vec<_> = outputs[1..].iter().map(|(_, relayer)| *relayer).collect();
+		elected.sort();
+		run_to_block(11);
+		assert_eq!(Dorr::active_relayers(), elected);
+		assert_eq!(Dorr::sorted_active_relayers(), elected);
+		assert!(!Dorr::is_active_relayer(&inactive));
+		assert_eq!(active_set_changes(), vec![(3, elected.clone())]);
+
+		// Results submitted during the epoch don't change its active set
+		let (val, proof) = vrf_result(inactive);
+		assert_ok!(Dorr::set_vrf_results(Origin::signed(inactive), val, proof));
+		run_to_block(15);
+		assert_eq!(Dorr::active_relayers(), elected);
+
+		// Relayers leaving are removed straight away
+		assert_ok!(Dorr::purge_pk(Origin::signed(elected[0])));
+		assert!(!Dorr::is_active_relayer(&elected[0]));
+		assert_eq!(active_set_changes().last(), Some(&(3, vec![elected[1]])));
+
+		run_to_block(16);
+		assert_eq!(Dorr::active_relayers(), vec![inactive]);
+		assert_eq!(active_set_changes().last(), Some(&(4, vec![inactive])));
+
+		// The event is only deposited when the set changes
+		let (val, proof) = vrf_result(inactive);
+		assert_ok!(Dorr::set_vrf_results(Origin::signed(inactive), val, proof));
+		run_to_block(21);
+		assert_eq!(Dorr::active_relayers(), vec![inactive]);
+		assert_eq!(active_set_changes().len(), 3);
+	});
+}
+
+#[test]
+fn expired_results_are_cleared() {
+	new_test_ext(1).execute_with(|| {
+		assert_ok!(register_pk(ALICE));
+		assert_ok!(register_pk(BOB));
+		run_to_block(6);
+		for relayer in [ALICE, BOB].iter() {
+			let (val, proof) = vrf_result(*relayer);
+			assert_ok!(Dorr::set_vrf_results(Origin::signed(*relayer), val, proof));
+		}
+		run_to_block(11);
+		let (val, proof) = vrf_result(BOB);
+		assert_ok!(Dorr::set_vrf_results(Origin::signed(BOB), val, proof));
+
+		// Results elected for epoch 3 expire at the start of epoch 5
+		run_to_block(20);
+		assert!(!Dorr::vrf_results(ALICE).val.is_empty());
+		run_to_block(21);
+		assert!(Dorr::vrf_results(ALICE).val.is_empty());
+		assert!(Dorr::vrf_results(ALICE).proof.is_empty());
+		assert_eq!(Dorr::vrf_results(ALICE).pk, public_key(ALICE));
+		assert!(<EpochToRandomness<Test>>::contains_key(3));
+		assert!(!<EpochToRandomness<Test>>::contains_key(2));
+		// Bob's result elected for epoch 4 is kept until epoch 6
+		assert!(!Dorr::vrf_results(BOB).val.is_empty());
+		run_to_block(26);
+		assert!(Dorr::vrf_results(BOB).val.is_empty());
+	});
+}
+
+#[test]
+fn epoch_randomness_is_fixed_on_first_block() {
+	new_test_ext(1).execute_with(|| {
+		run_to_block(6);
+		assert!(<EpochToRandomness<Test>>::contains_key(2));
+
+		let randomness = H256::repeat_byte(1);
+		<EpochToRandomness<Test>>::insert(2, randomness);
+		run_to_block(10);
+		assert_eq!(Dorr::get_public_randomness(2), randomness);
+		run_to_block(11);
+		assert!(<EpochToRandomness<Test>>::contains_key(3));
+		assert_eq!(Dorr::get_public_randomness(2), randomness);
+	});
+}
+
+#[test]
+fn migrate_to_epoch_bound_results() {
+	new_test_ext(1).execute_with(|| {
+		// A result stored without an election epoch
+		let old_result = (public_key(ALICE), vec![1u8; 32], vec![2u8; 64]);
+		unhashed::put(&<VrfResults<Test>>::hashed_key_for(ALICE), &old_result);
+		// Keys weren't bound to an account, so BOB and CHARLIE could register the same key
+		let shared_result = (public_key(BOB), vec![1u8; 32], vec![2u8; 64]);
+		unhashed::put(&<VrfResults<Test>>::hashed_key_for(BOB), &shared_result);
+		unhashed::put(&<VrfResults<Test>>::hashed_key_for(CHARLIE), &shared_result);
+		<PkToEpoch<Test>>::insert(public_key(BOB), 0);
+		assert_eq!(StorageVersion::get(), Releases::V1UnboundKeys);
+
+		Dorr::on_runtime_upgrade();
+
+		// The result is kept for the current epoch, and its relayer stays active
+		let result = Dorr::vrf_results(ALICE);
+		assert_eq!(result.pk, public_key(ALICE));
+		assert_eq!(result.val, vec![1u8; 32]);
+		assert_eq!(result.proof, vec![2u8; 64]);
+		assert_eq!(result.epoch, Dorr::get_current_epoch());
+		assert_eq!(Dorr::pk_owner(public_key(ALICE)), Some(ALICE));
+		assert_eq!(Dorr::active_relayers(), vec![ALICE]);
+		assert!(Dorr::is_active(public_key(ALICE)));
+		// The shared key is removed from both accounts
+		assert!(Dorr::vrf_results(BOB).pk.is_empty());
+		assert!(Dorr::vrf_results(CHARLIE).pk.is_empty());
+		assert_eq!(Dorr::pk_owner(public_key(BOB)), None);
+		assert!(!<PkToEpoch<Test>>::contains_key(public_key(BOB)));
+		assert_eq!(StorageVersion::get(), Releases::V3EpochBoundResults);
+
+		// Migrations are applied once
+		<VrfResults<Test>>::mutate(ALICE, |result| result.val = vec![3; 32]);
+		Dorr::on_runtime_upgrade();
+		assert_eq!(Dorr::vrf_results(ALICE).val, vec![3; 32]);
+
+		// The relayers elected for the next epoch submit new results
+		run_to_block(Dorr::get_current_epoch() * 5 + 1);
+		assert!(Dorr::active_relayers().is_empty());
+	});
+}
+
+#[test]
+fn migrate_drops_ineligible_results() {
+	new_test_ext(1).execute_with(|| {
+		// Keys are already owned, BOB and CHARLIE left the candidates before the upgrade
+		StorageVersion::put(Releases::V2OwnedKeys);
+		for who in [ALICE, BOB, CHARLIE].iter() {
+			let old_result = (public_key(*who), vec![1u8; 32], vec![2u8; 64]);
+			unhashed::put(&<VrfResults<Test>>::hashed_key_for(who), &old_result);
+			<PkToEpoch<Test>>::insert(public_key(*who), 0);
+		}
+		<PkOwners<Test>>::insert(public_key(ALICE), ALICE);
+		<PkOwners<Test>>::insert(public_key(BOB), BOB);
+		Candidates::set(vec![ALICE]);
+
+		Dorr::on_runtime_upgrade();
 
-		let pk_bob: String = String::from("8a3cac9282ba021cc6090f6ddfc826383300facb2101d6c736d794a5b25aa060");
-		assert_ok!(Dorr::set_pk(Origin::signed(BOB), pk_bob.from_hex().unwrap()));
+		// Only the eligible relayer keeps its result and key, and is elected
+		assert_eq!(Dorr::vrf_results(ALICE).epoch, Dorr::get_current_epoch());
+		assert_eq!(Dorr::pk_owner(public_key(ALICE)), Some(ALICE));
+		assert_eq!(Dorr::active_relayers(), vec![ALICE]);
+		for who in [BOB, CHARLIE].iter() {
+			assert!(!<VrfResults<Test>>::contains_key(who));
+			assert_eq!(Dorr::pk_owner(public_key(*who)), None);
+			assert!(!<PkToEpoch<Test>>::contains_key(public_key(*who)));
+		}
+		assert_eq!(StorageVersion::get(), Releases::V3EpochBoundResults);
+	});
+}
 
-		let pk_charlie: String = String::from("e062f3b7ff6d5de1339ccb295d7202c440e9d8bf421ca30ea8c37eae0a7ef559");
-		assert_ok!(Dorr::set_pk(Origin::signed(CHARLIE), pk_charlie.from_hex().unwrap()));
+#[test]
+fn set_pk_rejects_invalid_keys() {
+	new_test_ext(1).execute_with(|| {
+		let signature = pk_signature(&keypair(ALICE), ALICE);
+		for pk in [vec![], vec![1; 31], vec![1; 33], vec![0xff; 32]].iter() {
+			// The last key has the right length, but is not a valid point
+			assert_noop!(
+				Dorr::set_pk(Origin::signed(ALICE), pk.clone(), signature.clone()),
+				Error::<Test>::InvalidPublicKey
+			);
+		}
+
+		assert_ok!(Dorr::set_pk(Origin::signed(ALICE), public_key(ALICE), signature));
+	});
+}
 
+#[test]
+fn set_pk_requires_proof_of_possession() {
+	new_test_ext(1).execute_with(|| {
+		// Signed by another key
+		assert_noop!(
+			Dorr::set_pk(Origin::signed(ALICE), public_key(BOB), pk_signature(&keypair(ALICE), ALICE)),
+			Error::<Test>::InvalidPkSignature
+		);
+		// Signed for another account
+		assert_noop!(
+			Dorr::set_pk(Origin::signed(ALICE), public_key(BOB), pk_signature(&keypair(BOB), BOB)),
+			Error::<Test>::InvalidPkSignature
+		);
+		// Signed in a previous epoch
+		let signature = pk_signature(&keypair(ALICE), ALICE);
 		run_to_block(6);
+		assert_noop!(
+			Dorr::set_pk(Origin::signed(ALICE), public_key(ALICE), signature),
+			Error::<Test>::InvalidPkSignature
+		);
+		assert_noop!(
+			Dorr::set_pk(Origin::signed(ALICE), public_key(ALICE), sr25519::Signature::from_raw([0; 64])),
+			Error::<Test>::InvalidPkSignature
+		);
+
+		assert_ok!(register_pk(ALICE));
+		assert_eq!(Dorr::vrf_results(ALICE).pk, public_key(ALICE));
+	});
+}
 
-		assert_ok!(Dorr::set_vrf_results(
-			Origin::signed(ALICE),
-			String::from("dcd0f3a7d0af4a1336b7cad05ffedd3486ca88c6e32eb096b301dced2ae43f5a").from_hex().unwrap(),
-			String::from("fa44dbfe6f3d4b49b623777b28412fac2168a463360ef0b531fdb70a76643b07910a8a616f00861a6399d70477918d5cf04e18a7fe298779eae862003027f302").from_hex().unwrap())
+#[test]
+fn pk_is_registered_by_one_account() {
+	new_test_ext(1).execute_with(|| {
+		assert_ok!(register_pk(ALICE));
+		assert_eq!(Dorr::pk_owner(public_key(ALICE)), Some(ALICE));
+
+		// Even with a valid signature, another account can't take over the key
+		let signature = pk_signature(&keypair(ALICE), BOB);
+		assert_noop!(
+			Dorr::set_pk(Origin::signed(BOB), public_key(ALICE), signature.clone()),
+			Error::<Test>::PkAlreadyInUse
 		);
+		// Registering the same key again is fine
+		assert_ok!(register_pk(ALICE));
+
+		// The key becomes available once Alice switches to another key
+		assert_ok!(Dorr::set_pk(Origin::signed(ALICE), public_key(CHARLIE), pk_signature(&keypair(CHARLIE), ALICE)));
+		assert_eq!(Dorr::pk_owner(public_key(CHARLIE)), Some(ALICE));
+		assert!(!<PkOwners<Test>>::contains_key(public_key(ALICE)));
+		assert!(!<PkToEpoch<Test>>::contains_key(public_key(ALICE)));
+		assert_ok!(Dorr::set_pk(Origin::signed(BOB), public_key(ALICE), signature));
+		assert_eq!(Dorr::pk_owner(public_key(ALICE)), Some(BOB));
+
+		// Or purges it
+		assert_ok!(Dorr::purge_pk(Origin::signed(ALICE)));
+		assert_eq!(Dorr::pk_owner(public_key(CHARLIE)), None);
+		assert_ok!(register_pk(CHARLIE));
+		assert_eq!(Dorr::pk_owner(public_key(CHARLIE)), Some(CHARLIE));
+	});
+}
+
+#[test]
+fn only_eligible_candidates_take_part() {
+	new_test_ext(1).execute_with(|| {
+		assert_ok!(register_pk(ALICE));
+		assert_ok!(register_pk(BOB));
+		Candidates::set(vec![ALICE, CHARLIE]);
 
+		assert_noop!(register_pk(BOB), Error::<Test>::NotEligible);
+		run_to_block(6);
+		let (val, proof) = vrf_result(BOB);
+		assert_noop!(Dorr::set_vrf_results(Origin::signed(BOB), val, proof), Error::<Test>::NotEligible);
+
+		let (val, proof) = vrf_result(ALICE);
+		assert_ok!(Dorr::set_vrf_results(Origin::signed(ALICE), val, proof));
+		run_to_block(11);
+		assert_eq!(Dorr::sorted_active_relayers(), vec![ALICE]);
+	});
+}
+
+#[test]
+fn remove_candidate_clears_key_and_result() {
+	new_test_ext(1).execute_with(|| {
+		assert_ok!(register_pk(ALICE));
+		run_to_block(6);
+		let (val, proof) = vrf_result(ALICE);
+		assert_ok!(Dorr::set_vrf_results(Origin::signed(ALICE), val, proof));
 		run_to_block(11);
+		assert!(Dorr::is_active(public_key(ALICE)));
+
+		Dorr::remove_candidate(&ALICE);
+		assert!(!<VrfResults<Test>>::contains_key(ALICE));
+		assert!(!<PkOwners<Test>>::contains_key(public_key(ALICE)));
+		assert!(!<PkToEpoch<Test>>::contains_key(public_key(ALICE)));
+		assert!(Dorr::sorted_active_relayers().is_empty());
+
+		// The key can be registered again
+		assert_ok!(register_pk(ALICE));
+	});
+}
+
+#[test]
+fn set_vrf_results_rejects_malformed_results() {
+	new_test_ext(1).execute_with(|| {
+		assert_ok!(register_pk(ALICE));
+		run_to_block(6);
 
-		assert_ok!(Dorr::set_vrf_results(
-			Origin::signed(BOB),
-			String::from("10a343aaa12503ee7e004a7c56eb6f3956cba77f38a62a6e9544daa7ab07a913").from_hex().unwrap(),
-			String::from("c8c4383bcf63585f6ae816e05779b13dc130af4c13c3869e5c59b4dffd0283046b3e62e7a09ed59a15246eeb7326046fcd48203d6a723375f5e9084fdba2a807").from_hex().unwrap())
+		let (val, proof) = vrf_result(ALICE);
+		assert_noop!(
+			Dorr::set_vrf_results(Origin::signed(ALICE), val[1..].to_vec(), proof.clone()),
+			Error::<Test>::MalformedVrfOutput
+		);
+		assert_noop!(
+			Dorr::set_vrf_results(Origin::signed(ALICE), val.clone(), proof[1..].to_vec()),
+			Error::<Test>::MalformedVrfProof
+		);
+		// A proof for another output
+		let mut other_proof = proof.clone();
+		other_proof[0] ^= 1;
+		assert_noop!(
+			Dorr::set_vrf_results(Origin::signed(ALICE), val.clone(), other_proof),
+			Error::<Test>::BadVrfProof
 		);
 
-		run_to_block(16);
+		assert_ok!(Dorr::set_vrf_results(Origin::signed(ALICE), val, proof));
+	});
+}
+
+#[test]
+fn set_vrf_results_rejects_purged_key() {
+	new_test_ext(1).execute_with(|| {
+		assert_ok!(register_pk(ALICE));
+		run_to_block(6);
+		let (val, proof) = vrf_result(ALICE);
+		assert_ok!(Dorr::purge_pk(Origin::signed(ALICE)));
 
-		assert_ok!(Dorr::set_vrf_results(
-			Origin::signed(CHARLIE),
-			String::from("22ed779046c565be3c8f50c6e63cf7e6786a15de7283b86cac53528a2006516c").from_hex().unwrap(),
-			String::from("f0e53ab529aa808395b50842237b7e6ebae0a0694046470aa66336cd4b8b6700846706d0bf9bfbfb72e82f54423c94b0871f9272d1763169921d9c6faa1a350d").from_hex().unwrap())
+		assert_noop!(
+			Dorr::set_vrf_results(Origin::signed(ALICE), val, proof),
+			Error::<Test>::InvalidPublicKey
 		);
+	});
+}
+
+#[test]
+fn fuzz_set_pk() {
+	new_test_ext(1).execute_with(|| {
+		let mut fuzzer = Fuzzer(0);
+		for _ in 0..500 {
+			let pk = fuzzer.bytes_around(32);
+			assert_err_in(
+				Dorr::set_pk(Origin::signed(ALICE), pk, fuzzer.signature()),
+				vec![Error::InvalidPublicKey, Error::InvalidPkSignature],
+			);
+		}
+		assert!(!<VrfResults<Test>>::contains_key(ALICE));
+	});
+}
+
+#[test]
+fn fuzz_set_vrf_results() {
+	new_test_ext(1).execute_with(|| {
+		assert_ok!(register_pk(ALICE));
+		run_to_block(6);
 
-		assert_eq!(Dorr::is_active(pk_alice.from_hex().unwrap()), true);
-		assert_eq!(Dorr::is_active(pk_bob.from_hex().unwrap()), false);
-		assert_eq!(Dorr::is_active(pk_charlie.from_hex().unwrap()), true);
+		let mut fuzzer = Fuzzer(1 << 32);
+		for _ in 0..500 {
+			let val = fuzzer.bytes_around(32);
+			let proof = fuzzer.bytes_around(64);
+			assert_err_in(
+				Dorr::set_vrf_results(Origin::signed(ALICE), val, proof),
+				vec![Error::MalformedVrfOutput, Error::MalformedVrfProof, Error::BadVrfProof],
+			);
+		}
+		assert!(<VrfResults<Test>>::get(ALICE).val.is_empty());
 	});
-}
\ No newline at end of file
+}
+
+#[test]
+fn fuzz_random_calls() {
+	new_test_ext(1).execute_with(|| {
+		let mut fuzzer = Fuzzer(2 << 32);
+		let accounts = [ALICE, BOB, CHARLIE];
+		for block in 1..200 {
+			run_to_block(block);
+			let who = Origin::signed(accounts[fuzzer.below(accounts.len())]);
+			let result = match fuzzer.below(3) {
+				0 => Dorr::set_pk(who, fuzzer.bytes_around(32), fuzzer.signature()),
+				1 => Dorr::purge_pk(who),
+				_ => Dorr::set_vrf_results(who, fuzzer.bytes_around(32), fuzzer.bytes_around(64)),
+			};
+			if result.is_err() {
+				assert_err_in(result, vec![
+					Error::PkIsNotSetted,
+					Error::InvalidEpochTooEarly,
+					Error::InvalidPublicKey,
+					Error::InvalidPkSignature,
+					Error::MalformedVrfOutput,
+					Error::MalformedVrfProof,
+					Error::BadVrfProof,
+				]);
+			}
+			// Random results are never accepted
+			assert!(Dorr::sorted_active_relayers().is_empty());
+		}
+	});
+}
@@ -0,0 +1,72 @@
+//! Benchmarks for the example ERC721 pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_system::RawOrigin;
+use sp_std::vec;
+
+const SEED: u32 = 0;
+/// Upper bound of the token metadata size used in the benchmarks
+const MAX_METADATA_SIZE: u32 = 1024;
+
+benchmarks! {
+    _ { }
+
+    mint {
+        let m in 0 .. MAX_METADATA_SIZE;
+        let owner: T::AccountId = account("owner", 0, SEED);
+        let id = TokenId::one();
+    }: _(RawOrigin::Root, owner.clone(), id, vec![1u8; m as usize])
+    verify {
+        assert_eq!(Module::<T>::owner_of(id), Some(owner));
+    }
+
+    transfer {
+        let caller: T::AccountId = whitelisted_caller();
+        let to: T::AccountId = account("to", 0, SEED);
+        let id = TokenId::one();
+        Module::<T>::mint_token(caller.clone(), id, vec![1u8; MAX_METADATA_SIZE as usize])?;
+    }: _(RawOrigin::Signed(caller), to.clone(), id)
+    verify {
+        assert_eq!(Module::<T>::owner_of(id), Some(to));
+    }
+
+    burn {
+        let owner: T::AccountId = account("owner", 0, SEED);
+        let id = TokenId::one();
+        Module::<T>::mint_token(owner, id, vec![1u8; MAX_METADATA_SIZE as usize])?;
+    }: _(RawOrigin::Root, id)
+    verify {
+        assert_eq!(Module::<T>::tokens(id), None);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{new_test_ext, Test};
+    use frame_support::assert_ok;
+
+    #[test]
+    fn mint() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_mint::<Test>());
+        });
+    }
+
+    #[test]
+    fn transfer() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_transfer::<Test>());
+        });
+    }
+
+    #[test]
+    fn burn() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_burn::<Test>());
+        });
+    }
+}
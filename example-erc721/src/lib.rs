@@ -4,13 +4,16 @@
 use codec::{Decode, Encode};
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage, dispatch::DispatchResult, ensure,
-    traits::Get,
+    traits::Get, weights::Weight,
 };
 use frame_system::{self as system, ensure_root, ensure_signed};
 use sp_core::U256;
 use sp_runtime::RuntimeDebug;
 use sp_std::prelude::*;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+mod default_weights;
 mod mock;
 mod tests;
 
@@ -22,12 +25,21 @@ pub struct Erc721Token {
     pub metadata: Vec<u8>,
 }
 
+pub trait WeightInfo {
+    fn mint(m: u32) -> Weight;
+    fn transfer() -> Weight;
+    fn burn() -> Weight;
+}
+
 pub trait Trait: system::Trait {
     type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
 
     /// Some identifier for this token type, possibly the originating ethereum address.
     /// This is not explicitly used for anything, but may reflect the bridge's notion of resource ID.
     type Identifier: Get<[u8; 32]>;
+
+    /// Weight information for extrinsics in this pallet
+    type WeightInfo: WeightInfo;
 }
 
 decl_event! {
@@ -72,7 +84,7 @@ decl_module! {
         fn deposit_event() = default;
 
         /// Creates a new token with the given token ID and metadata, and gives ownership to owner
-        #[weight = 195_000_000]
+        #[weight = T::WeightInfo::mint(metadata.len() as u32)]
         pub fn mint(origin, owner: T::AccountId, id: TokenId, metadata: Vec<u8>) -> DispatchResult {
             ensure_root(origin)?;
 
@@ -82,7 +94,7 @@ decl_module! {
         }
 
         /// Changes ownership of a token sender owns
-        #[weight = 195_000_000]
+        #[weight = T::WeightInfo::transfer()]
         pub fn transfer(origin, to: T::AccountId, id: TokenId) -> DispatchResult {
             let sender = ensure_signed(origin)?;
 
@@ -92,7 +104,7 @@ decl_module! {
         }
 
         /// Remove token from the system
-        #[weight = 195_000_000]
+        #[weight = T::WeightInfo::burn()]
         pub fn burn(origin, id: TokenId) -> DispatchResult {
             ensure_root(origin)?;
 
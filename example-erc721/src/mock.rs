@@ -75,6 +75,7 @@ parameter_types! {
 impl Trait for Test {
     type Event = Event;
     type Identifier = Erc721Id;
+    type WeightInfo = ();
 }
 
 pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
@@ -0,0 +1,108 @@
+//! Benchmarks for the example pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+use sp_std::{convert::TryFrom, vec};
+
+const SEED: u32 = 0;
+/// Upper bound of the external recipient address size used in the benchmarks
+const MAX_RECIPIENT_SIZE: u32 = 256;
+/// Upper bound of the token metadata size used in the benchmarks
+const MAX_METADATA_SIZE: u32 = 1024;
+
+/// Whitelists a destination chain for outbound transfers.
+fn setup_dest_chain<T: Trait>() -> Result<bridge::ChainIdOf<T>, &'static str> {
+    let id: u32 = <T as bridge::Trait>::ChainIdentity::get().into();
+    let dest_id = <T as bridge::Trait>::ChainId::try_from(id.wrapping_add(1))
+        .unwrap_or_else(|_| <T as bridge::Trait>::ChainId::from(0));
+    <bridge::Module<T>>::whitelist(dest_id)?;
+    Ok(dest_id)
+}
+
+/// Registers an enabled resource of the given kind for outbound transfers.
+fn setup_resource<T: Trait>(
+    id: bridge::ResourceId,
+    kind: bridge::ResourceKind,
+) -> Result<(), &'static str> {
+    let resource = bridge::Resource {
+        method: vec![],
+        kind,
+        destinations: vec![],
+        enabled: true,
+        call_index: [0, 0],
+    };
+    <bridge::Module<T>>::register_resource(id, resource)?;
+    Ok(())
+}
+
+/// Returns an origin accepted by `BridgeOrigin` and its account
+fn bridge_origin<T: Trait>() -> Result<(T::Origin, T::AccountId), &'static str> {
+    let origin = T::BridgeOrigin::successful_origin();
+    let source = T::BridgeOrigin::ensure_origin(origin.clone()).map_err(|_| "bad origin")?;
+    Ok((origin, source))
+}
+
+benchmarks! {
+    _ { }
+
+    transfer_hash {
+        let caller: T::AccountId = whitelisted_caller();
+        let dest_id = setup_dest_chain::<T>()?;
+        setup_resource::<T>(T::HashId::get(), bridge::ResourceKind::Generic)?;
+    }: _(RawOrigin::Signed(caller), T::Hash::default(), dest_id)
+    verify {
+        assert_eq!(<bridge::Module<T>>::chains(dest_id), Some(1));
+    }
+
+    transfer_native {
+        let r in 0 .. MAX_RECIPIENT_SIZE;
+        let caller: T::AccountId = whitelisted_caller();
+        <T as Trait>::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+        let dest_id = setup_dest_chain::<T>()?;
+        setup_resource::<T>(T::NativeTokenId::get(), bridge::ResourceKind::Fungible { decimals: 18 })?;
+        let amount = <T as Trait>::Currency::minimum_balance();
+    }: _(RawOrigin::Signed(caller), amount, vec![1u8; r as usize], dest_id)
+    verify {
+        assert_eq!(<bridge::Module<T>>::chains(dest_id), Some(1));
+    }
+
+    transfer_erc721 {
+        let r in 0 .. MAX_RECIPIENT_SIZE;
+        let caller: T::AccountId = whitelisted_caller();
+        let dest_id = setup_dest_chain::<T>()?;
+        setup_resource::<T>(T::Erc721Id::get(), bridge::ResourceKind::NonFungible)?;
+        let token_id = U256::one();
+        <erc721::Module<T>>::mint_token(caller.clone(), token_id, vec![1u8; MAX_METADATA_SIZE as usize])?;
+    }: _(RawOrigin::Signed(caller), vec![1u8; r as usize], token_id, dest_id)
+    verify {
+        assert_eq!(<erc721::Module<T>>::tokens(token_id), None);
+    }
+
+    transfer {
+        let (origin, source) = bridge_origin::<T>()?;
+        <T as Trait>::Currency::make_free_balance_be(&source, BalanceOf::<T>::max_value());
+        let to: T::AccountId = account("to", 0, SEED);
+        let amount = <T as Trait>::Currency::minimum_balance();
+    }: _(origin, to.clone(), amount, <T as Trait>::NativeTokenId::get())
+    verify {
+        assert_eq!(<T as Trait>::Currency::free_balance(&to), amount);
+    }
+
+    remark {
+        let (origin, _) = bridge_origin::<T>()?;
+    }: _(origin, T::Hash::default(), <T as Trait>::HashId::get())
+
+    mint_erc721 {
+        let m in 0 .. MAX_METADATA_SIZE;
+        let (origin, _) = bridge_origin::<T>()?;
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+        let token_id = U256::one();
+    }: _(origin, recipient.clone(), token_id, vec![1u8; m as usize], <T as Trait>::Erc721Id::get())
+    verify {
+        assert_eq!(<erc721::Module<T>>::owner_of(token_id), Some(recipient));
+    }
+}
@@ -0,0 +1,40 @@
+//! Default weights for the example pallet. See the Weights section of the README.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::weights::{constants::RocksDbWeight as DbWeight, Weight};
+
+impl crate::WeightInfo for () {
+    fn transfer_hash() -> Weight {
+        (40_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(8 as Weight))
+            .saturating_add(DbWeight::get().writes(4 as Weight))
+    }
+    fn transfer_native(r: u32) -> Weight {
+        (95_000_000 as Weight)
+            .saturating_add((1_000 as Weight).saturating_mul(r as Weight))
+            .saturating_add(DbWeight::get().reads(11 as Weight))
+            .saturating_add(DbWeight::get().writes(6 as Weight))
+    }
+    fn transfer_erc721(r: u32) -> Weight {
+        (90_000_000 as Weight)
+            .saturating_add((1_000 as Weight).saturating_mul(r as Weight))
+            .saturating_add(DbWeight::get().reads(13 as Weight))
+            .saturating_add(DbWeight::get().writes(7 as Weight))
+    }
+    fn transfer() -> Weight {
+        (70_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn remark() -> Weight {
+        (15_000_000 as Weight)
+    }
+    fn mint_erc721(m: u32) -> Weight {
+        (45_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(m as Weight))
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(3 as Weight))
+    }
+}
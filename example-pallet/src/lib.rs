@@ -4,12 +4,18 @@
 use chainbridge as bridge;
 use example_erc721 as erc721;
 use frame_support::traits::{Currency, EnsureOrigin, ExistenceRequirement::AllowDeath, Get};
-use frame_support::{decl_error, decl_event, decl_module, dispatch::DispatchResult, ensure};
+use frame_support::{
+    decl_error, decl_event, decl_module, dispatch::DispatchResult, ensure, transactional,
+    weights::Weight,
+};
 use frame_system::{self as system, ensure_signed};
 use sp_arithmetic::traits::SaturatedConversion;
 use sp_core::U256;
 use sp_std::prelude::*;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+mod default_weights;
 mod mock;
 mod tests;
 
@@ -18,6 +24,15 @@ type ResourceId = bridge::ResourceId;
 type BalanceOf<T> =
     <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
 
+pub trait WeightInfo {
+    fn transfer_hash() -> Weight;
+    fn transfer_native(r: u32) -> Weight;
+    fn transfer_erc721(r: u32) -> Weight;
+    fn transfer() -> Weight;
+    fn remark() -> Weight;
+    fn mint_erc721(m: u32) -> Weight;
+}
+
 pub trait Trait: system::Trait + bridge::Trait + erc721::Trait {
     type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
     /// Specifies the origin check provided by the bridge for calls that can only be called by the bridge pallet
@@ -30,6 +45,9 @@ pub trait Trait: system::Trait + bridge::Trait + erc721::Trait {
     type HashId: Get<ResourceId>;
     type NativeTokenId: Get<ResourceId>;
     type Erc721Id: Get<ResourceId>;
+
+    /// Weight information for extrinsics in this pallet
+    type WeightInfo: WeightInfo;
 }
 
 decl_event! {
@@ -59,39 +77,45 @@ decl_module! {
         //
 
         /// Transfers an arbitrary hash to a (whitelisted) destination chain.
-        #[weight = 195_000_000]
-        pub fn transfer_hash(origin, hash: T::Hash, dest_id: bridge::ChainId) -> DispatchResult {
-            ensure_signed(origin)?;
+        #[weight = <T as Trait>::WeightInfo::transfer_hash()]
+        pub fn transfer_hash(origin, hash: T::Hash, dest_id: bridge::ChainIdOf<T>) -> DispatchResult {
+            let source = ensure_signed(origin)?;
 
             let resource_id = T::HashId::get();
             let metadata: Vec<u8> = hash.as_ref().to_vec();
-            <bridge::Module<T>>::transfer_generic(dest_id, resource_id, metadata)
+            <bridge::Module<T>>::transfer_generic(&source, dest_id, resource_id, metadata)
         }
 
         /// Transfers some amount of the native token to some recipient on a (whitelisted) destination chain.
-        #[weight = 195_000_000]
-        pub fn transfer_native(origin, amount: BalanceOf<T>, recipient: Vec<u8>, dest_id: bridge::ChainId) -> DispatchResult {
+        ///
+        /// The amount is returned to the sender if the bridge rejects the transfer.
+        #[weight = <T as Trait>::WeightInfo::transfer_native(recipient.len() as u32)]
+        #[transactional]
+        pub fn transfer_native(origin, amount: BalanceOf<T>, recipient: Vec<u8>, dest_id: bridge::ChainIdOf<T>) -> DispatchResult {
             let source = ensure_signed(origin)?;
             ensure!(<bridge::Module<T>>::chain_whitelisted(dest_id), Error::<T>::InvalidTransfer);
             let bridge_id = <bridge::Module<T>>::account_id();
-            T::Currency::transfer(&source, &bridge_id, amount.into(), AllowDeath)?;
+            <T as Trait>::Currency::transfer(&source, &bridge_id, amount.into(), AllowDeath)?;
 
             let resource_id = T::NativeTokenId::get();
-            <bridge::Module<T>>::transfer_fungible(dest_id, resource_id, recipient, U256::from(amount.saturated_into()))
+            <bridge::Module<T>>::transfer_fungible(&source, dest_id, resource_id, recipient, U256::from(amount.saturated_into()))
         }
 
         /// Transfer a non-fungible token (erc721) to a (whitelisted) destination chain.
-        #[weight = 195_000_000]
-        pub fn transfer_erc721(origin, recipient: Vec<u8>, token_id: U256, dest_id: bridge::ChainId) -> DispatchResult {
+        ///
+        /// The token isn't burned if the bridge rejects the transfer.
+        #[weight = <T as Trait>::WeightInfo::transfer_erc721(recipient.len() as u32)]
+        #[transactional]
+        pub fn transfer_erc721(origin, recipient: Vec<u8>, token_id: U256, dest_id: bridge::ChainIdOf<T>) -> DispatchResult {
             let source = ensure_signed(origin)?;
             ensure!(<bridge::Module<T>>::chain_whitelisted(dest_id), Error::<T>::InvalidTransfer);
             match <erc721::Module<T>>::tokens(&token_id) {
                 Some(token) => {
-                    <erc721::Module<T>>::burn_token(source, token_id)?;
+                    <erc721::Module<T>>::burn_token(source.clone(), token_id)?;
                     let resource_id = T::Erc721Id::get();
                     let tid: &mut [u8] = &mut[0; 32];
                     token_id.to_big_endian(tid);
-                    <bridge::Module<T>>::transfer_nonfungible(dest_id, resource_id, tid.to_vec(), recipient, token.metadata)
+                    <bridge::Module<T>>::transfer_nonfungible(&source, dest_id, resource_id, tid.to_vec(), recipient, token.metadata)
                 }
                 None => Err(Error::<T>::InvalidTransfer)?
             }
@@ -102,7 +126,7 @@ decl_module! {
         //
 
         /// Executes a simple currency transfer using the bridge account as the source
-        #[weight = 195_000_000]
+        #[weight = <T as Trait>::WeightInfo::transfer()]
         pub fn transfer(origin, to: T::AccountId, amount: BalanceOf<T>, r_id: ResourceId) -> DispatchResult {
             let source = T::BridgeOrigin::ensure_origin(origin)?;
             <T as Trait>::Currency::transfer(&source, &to, amount.into(), AllowDeath)?;
@@ -110,7 +134,7 @@ decl_module! {
         }
 
         /// This can be called by the bridge to demonstrate an arbitrary call from a proposal.
-        #[weight = 195_000_000]
+        #[weight = <T as Trait>::WeightInfo::remark()]
         pub fn remark(origin, hash: T::Hash, r_id: ResourceId) -> DispatchResult {
             T::BridgeOrigin::ensure_origin(origin)?;
             Self::deposit_event(RawEvent::Remark(hash));
@@ -118,7 +142,7 @@ decl_module! {
         }
 
         /// Allows the bridge to issue new erc721 tokens
-        #[weight = 195_000_000]
+        #[weight = <T as Trait>::WeightInfo::mint_erc721(metadata.len() as u32)]
         pub fn mint_erc721(origin, recipient: T::AccountId, id: U256, metadata: Vec<u8>, r_id: ResourceId) -> DispatchResult {
             T::BridgeOrigin::ensure_origin(origin)?;
             <erc721::Module<T>>::mint_token(recipient, id, metadata)?;
@@ -1,4 +1,70 @@
-// #![cfg(test)]
+#![cfg(test)]
+
+use super::mock::{
+    new_test_ext, Balances, Bridge, Erc721, Erc721Id, Example, NativeTokenId, Origin, Test,
+    ENDOWED_BALANCE, RELAYER_A,
+};
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+
+fn bridge_resource(kind: bridge::ResourceKind) -> bridge::Resource {
+    bridge::Resource {
+        method: vec![],
+        kind,
+        destinations: vec![],
+        enabled: true,
+        call_index: [0, 0],
+    }
+}
+
+#[test]
+fn rejected_native_transfer_keeps_funds() {
+    new_test_ext().execute_with(|| {
+        let dest_chain = 0;
+        let outbound = bridge::PauseScope::Direction(bridge::BridgeDirection::Outbound);
+        let resource = bridge_resource(bridge::ResourceKind::Fungible { decimals: 18 });
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_chain));
+        assert_ok!(Bridge::set_resource(Origin::root(), NativeTokenId::get(), resource));
+        assert_ok!(Bridge::pause(Origin::root(), outbound.clone()));
+
+        assert_noop!(
+            Example::transfer_native(Origin::signed(RELAYER_A), 100, vec![99], dest_chain),
+            bridge::Error::<Test>::DirectionPaused
+        );
+        assert_eq!(Balances::free_balance(RELAYER_A), ENDOWED_BALANCE);
+
+        assert_ok!(Bridge::unpause(Origin::root(), outbound));
+        assert_ok!(Example::transfer_native(Origin::signed(RELAYER_A), 100, vec![99], dest_chain));
+        assert_eq!(Balances::free_balance(RELAYER_A), ENDOWED_BALANCE - 100);
+    })
+}
+
+#[test]
+fn rejected_erc721_transfer_keeps_token() {
+    new_test_ext().execute_with(|| {
+        let dest_chain = 0;
+        let token_id = U256::from(100);
+        let paused = bridge::PauseScope::Resource(Erc721Id::get());
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_chain));
+        assert_ok!(Bridge::set_resource(
+            Origin::root(),
+            Erc721Id::get(),
+            bridge_resource(bridge::ResourceKind::NonFungible)
+        ));
+        assert_ok!(Erc721::mint(Origin::root(), RELAYER_A, token_id, vec![1, 2, 3]));
+        assert_ok!(Bridge::pause(Origin::root(), paused.clone()));
+
+        assert_noop!(
+            Example::transfer_erc721(Origin::signed(RELAYER_A), vec![99], token_id, dest_chain),
+            bridge::Error::<Test>::ResourcePaused
+        );
+        assert_eq!(Erc721::owner_of(token_id), Some(RELAYER_A));
+
+        assert_ok!(Bridge::unpause(Origin::root(), paused));
+        assert_ok!(Example::transfer_erc721(Origin::signed(RELAYER_A), vec![99], token_id, dest_chain));
+        assert_eq!(Erc721::tokens(token_id), None);
+    })
+}
 
 // use super::mock::{
 //     assert_events, balances, event_exists, expect_event, new_test_ext, Balances, Bridge, Call,
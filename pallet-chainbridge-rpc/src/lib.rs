@@ -0,0 +1,353 @@
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpc_derive::rpc;
+use jsonrpc_core::{ Error as RpcError, ErrorCode, Result };
+use serde::{ Deserialize, Serialize };
+
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{ Bytes, H256 };
+use sp_runtime::{ generic::BlockId, traits::{ Block as BlockT } };
+
+use chainbridge::{
+	ChainBridgeRuntimeApi, ChainConfig, DepositNonce, DepositPayload, DepositRecord, PauseScope, ProposalStatus,
+	ResourceKind,
+};
+
+/// A whitelisted chain and the nonce of its latest outbound deposit
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainInfo<ChainId> {
+	pub chain_id: ChainId,
+	pub nonce: DepositNonce,
+}
+
+/// A registered resource and the method it maps to
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceInfo<ChainId> {
+	pub resource_id: H256,
+	pub method: Bytes,
+	pub kind: ResourceKind,
+	pub destinations: Vec<ChainId>,
+	pub enabled: bool,
+	pub call_index: [u8; 2],
+}
+
+/// A proposal for an inbound deposit and its votes
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProposalInfo<AccountId, BlockNumber, Hash> {
+	pub prop_hash: Hash,
+	pub votes_for: Vec<AccountId>,
+	pub votes_against: Vec<AccountId>,
+	pub status: ProposalStatus,
+	pub expiry: BlockNumber,
+	pub voters: Vec<AccountId>,
+	pub resource_id: H256,
+}
+
+/// An outbound deposit recorded in storage
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositInfo<BlockNumber> {
+	pub nonce: DepositNonce,
+	pub resource_id: H256,
+	pub payload: DepositPayload,
+	pub block: BlockNumber,
+}
+
+impl<BlockNumber> DepositInfo<BlockNumber> {
+	fn new(nonce: DepositNonce, record: DepositRecord<BlockNumber>) -> Self {
+		DepositInfo {
+			nonce,
+			resource_id: H256::from(record.resource_id),
+			payload: record.payload,
+			block: record.block,
+		}
+	}
+}
+
+#[rpc]
+pub trait ChainBridgeRpcApi<BlockHash, AccountId, BlockNumber, Hash, ChainId> {
+	#[rpc(name = "chainbridge_getChains")]
+	fn get_chains(
+			&self,
+			at: Option<BlockHash>,
+	) -> Result<Vec<ChainInfo<ChainId>>>;
+
+	#[rpc(name = "chainbridge_getRelayers")]
+	fn get_relayers(
+			&self,
+			at: Option<BlockHash>,
+	) -> Result<Vec<AccountId>>;
+
+	#[rpc(name = "chainbridge_getRelayerThreshold")]
+	fn get_relayer_threshold(
+			&self,
+			src_id: ChainId,
+			at: Option<BlockHash>,
+	) -> Result<u32>;
+
+	#[rpc(name = "chainbridge_getResources")]
+	fn get_resources(
+			&self,
+			at: Option<BlockHash>,
+	) -> Result<Vec<ResourceInfo<ChainId>>>;
+
+	#[rpc(name = "chainbridge_getProposalStatus")]
+	fn get_proposal_status(
+			&self,
+			src_id: ChainId,
+			nonce: DepositNonce,
+			start: Option<Hash>,
+			limit: u32,
+			at: Option<BlockHash>,
+	) -> Result<Vec<ProposalInfo<AccountId, BlockNumber, Hash>>>;
+
+	#[rpc(name = "chainbridge_isPaused")]
+	fn is_paused(
+			&self,
+			scope: PauseScope<ChainId>,
+			at: Option<BlockHash>,
+	) -> Result<bool>;
+
+	#[rpc(name = "chainbridge_getPausedScopes")]
+	fn get_paused_scopes(
+			&self,
+			at: Option<BlockHash>,
+	) -> Result<Vec<PauseScope<ChainId>>>;
+
+	#[rpc(name = "chainbridge_getDeposit")]
+	fn get_deposit(
+			&self,
+			dest_id: ChainId,
+			nonce: DepositNonce,
+			at: Option<BlockHash>,
+	) -> Result<Option<DepositInfo<BlockNumber>>>;
+
+	#[rpc(name = "chainbridge_getDeposits")]
+	fn get_deposits(
+			&self,
+			dest_id: ChainId,
+			from: DepositNonce,
+			count: u32,
+			at: Option<BlockHash>,
+	) -> Result<Vec<DepositInfo<BlockNumber>>>;
+
+	#[rpc(name = "chainbridge_getChainConfig")]
+	fn get_chain_config(
+			&self,
+			id: ChainId,
+			at: Option<BlockHash>,
+	) -> Result<Option<ChainConfig<BlockNumber>>>;
+}
+
+/// A struct that implements the `ChainBridgeRpcApi`.
+pub struct ChainBridgeRpc<C, M> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<M>,
+}
+
+impl<C, M> ChainBridgeRpc<C, M> {
+	/// Create new `ChainBridgeRpc` instance with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+			Self { client, _marker: Default::default() }
+	}
+}
+
+/// Converts a runtime API error into an RPC error with the given code.
+fn runtime_error(code: i64, e: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(code),
+		message: "Runtime API call failed".into(),
+		data: Some(format!("{:?}", e).into()),
+	}
+}
+
+impl<C, Block, AccountId, BlockNumber, Hash, ChainId>
+	ChainBridgeRpcApi<<Block as BlockT>::Hash, AccountId, BlockNumber, Hash, ChainId> for ChainBridgeRpc<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: ChainBridgeRuntimeApi<Block, AccountId, BlockNumber, Hash, ChainId>,
+	AccountId: Codec,
+	BlockNumber: Codec,
+	Hash: Codec,
+	ChainId: Codec,
+{
+	fn get_chains(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<ChainInfo<ChainId>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash
+		));
+
+		let chains = api.get_chains(&at).map_err(|e| runtime_error(100600, e))?;
+		Ok(chains
+			.into_iter()
+			.map(|(chain_id, nonce)| ChainInfo { chain_id, nonce })
+			.collect())
+	}
+
+	fn get_relayers(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<AccountId>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash
+		));
+
+		api.get_relayers(&at).map_err(|e| runtime_error(100601, e))
+	}
+
+	fn get_relayer_threshold(
+		&self,
+		src_id: ChainId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<u32> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash
+		));
+
+		api.get_relayer_threshold(&at, src_id).map_err(|e| runtime_error(100602, e))
+	}
+
+	fn get_resources(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<ResourceInfo<ChainId>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash
+		));
+
+		let resources = api.get_resources(&at).map_err(|e| runtime_error(100603, e))?;
+		Ok(resources
+			.into_iter()
+			.map(|(id, resource)| ResourceInfo {
+				resource_id: H256::from(id),
+				method: resource.method.into(),
+				kind: resource.kind,
+				destinations: resource.destinations,
+				enabled: resource.enabled,
+				call_index: resource.call_index,
+			})
+			.collect())
+	}
+
+	fn get_proposal_status(
+		&self,
+		src_id: ChainId,
+		nonce: DepositNonce,
+		start: Option<Hash>,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<ProposalInfo<AccountId, BlockNumber, Hash>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash
+		));
+
+		let proposals = api.get_proposals(&at, src_id, nonce, start, limit).map_err(|e| runtime_error(100604, e))?;
+		Ok(proposals
+			.into_iter()
+			.map(|(prop_hash, votes)| ProposalInfo {
+				prop_hash,
+				votes_for: votes.votes_for,
+				votes_against: votes.votes_against,
+				status: votes.status,
+				expiry: votes.expiry,
+				voters: votes.voters,
+				resource_id: H256::from(votes.resource_id),
+			})
+			.collect())
+	}
+
+	fn is_paused(
+		&self,
+		scope: PauseScope<ChainId>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<bool> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash
+		));
+
+		api.is_paused(&at, scope).map_err(|e| runtime_error(100605, e))
+	}
+
+	fn get_paused_scopes(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<PauseScope<ChainId>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash
+		));
+
+		api.get_paused_scopes(&at).map_err(|e| runtime_error(100606, e))
+	}
+
+	fn get_deposit(
+		&self,
+		dest_id: ChainId,
+		nonce: DepositNonce,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<DepositInfo<BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash
+		));
+
+		let record = api.get_deposit(&at, dest_id, nonce).map_err(|e| runtime_error(100607, e))?;
+		Ok(record.map(|record| DepositInfo::new(nonce, record)))
+	}
+
+	fn get_deposits(
+		&self,
+		dest_id: ChainId,
+		from: DepositNonce,
+		count: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<DepositInfo<BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash
+		));
+
+		let records = api.get_deposits(&at, dest_id, from, count).map_err(|e| runtime_error(100608, e))?;
+		Ok(records
+			.into_iter()
+			.map(|(nonce, record)| DepositInfo::new(nonce, record))
+			.collect())
+	}
+
+	fn get_chain_config(
+		&self,
+		id: ChainId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<ChainConfig<BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash
+		));
+
+		api.get_chain_config(&at, id).map_err(|e| runtime_error(100609, e))
+	}
+}
@@ -0,0 +1,26 @@
+#!/usr/bin/env bash
+
+# Generates weights for the pallets of this repo from their benchmarks.
+#
+# NODE must be a node built with `--features runtime-benchmarks` whose runtime registers the
+# benchmarks of these pallets under their crate names. The generated files are written to
+# OUTPUT (default: ./weights), one per pallet, for the runtime to use as its WeightInfo.
+
+set -eux
+
+NODE="${NODE:?path to a node built with runtime-benchmarks}"
+OUTPUT="${OUTPUT:-./weights}"
+
+mkdir -p "$OUTPUT"
+
+for pallet in chainbridge dorr-pallet example-erc721 example-pallet simple-message-pallet; do
+	"$NODE" benchmark \
+		--chain dev \
+		--execution wasm \
+		--wasm-execution compiled \
+		--pallet "$pallet" \
+		--extrinsic '*' \
+		--steps 50 \
+		--repeat 20 \
+		--output "$OUTPUT/${pallet//-/_}.rs"
+done
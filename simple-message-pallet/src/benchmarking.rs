@@ -0,0 +1,67 @@
+//! Benchmarks for the simple message pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_support::traits::Get;
+use frame_system::RawOrigin;
+use sp_std::{convert::TryFrom, vec};
+
+/// Upper bound of the message size used in the benchmarks
+const MAX_MESSAGE_SIZE: u32 = 16 * 1024;
+
+benchmarks! {
+  _ { }
+
+  write_msg {
+    let m in 0 .. MAX_MESSAGE_SIZE;
+    let origin = T::BridgeOrigin::successful_origin();
+    let nonce: Nonce = 1;
+  }: { Module::<T>::write_msg(origin, vec![1u8; 20], nonce, vec![1u8; m as usize])? }
+  verify {
+    assert!(Inbox::<T>::contains_key(nonce));
+  }
+
+  send_msg {
+    let m in 0 .. MAX_MESSAGE_SIZE;
+    let caller: T::AccountId = whitelisted_caller();
+    let id: u32 = <T as bridge::Trait>::ChainIdentity::get().into();
+    let dest_id = <T as bridge::Trait>::ChainId::try_from(id.wrapping_add(1))
+      .unwrap_or_else(|_| <T as bridge::Trait>::ChainId::from(0));
+    <bridge::Module<T>>::whitelist(dest_id)?;
+    let resource = bridge::Resource {
+      method: b"SimpleMsg.send_msg".to_vec(),
+      kind: bridge::ResourceKind::Generic,
+      destinations: vec![],
+      enabled: true,
+      call_index: [0, 0],
+    };
+    <bridge::Module<T>>::register_resource(T::MessageId::get(), resource)?;
+  }: _(RawOrigin::Signed(caller), vec![1u8; m as usize], dest_id)
+  verify {
+    assert_eq!(Module::<T>::nonce(dest_id), 1);
+    assert_eq!(<bridge::Module<T>>::chains(dest_id), Some(1));
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::mock::{new_test_ext, Test};
+  use frame_support::assert_ok;
+
+  #[test]
+  fn write_msg() {
+    new_test_ext().execute_with(|| {
+      assert_ok!(test_benchmark_write_msg::<Test>());
+    });
+  }
+
+  #[test]
+  fn send_msg() {
+    new_test_ext().execute_with(|| {
+      assert_ok!(test_benchmark_send_msg::<Test>());
+    });
+  }
+}
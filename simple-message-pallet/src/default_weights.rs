@@ -0,0 +1,21 @@
+//! Default weights for the simple message pallet. See the Weights section of the README.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::weights::{constants::RocksDbWeight as DbWeight, Weight};
+
+impl crate::WeightInfo for () {
+  fn write_msg(m: u32) -> Weight {
+    (30_000_000 as Weight)
+      .saturating_add((2_000 as Weight).saturating_mul(m as Weight))
+      .saturating_add(DbWeight::get().reads(1 as Weight))
+      .saturating_add(DbWeight::get().writes(1 as Weight))
+  }
+  fn send_msg(m: u32) -> Weight {
+    (45_000_000 as Weight)
+      .saturating_add((2_000 as Weight).saturating_mul(m as Weight))
+      .saturating_add(DbWeight::get().reads(11 as Weight))
+      .saturating_add(DbWeight::get().writes(5 as Weight))
+  }
+}
@@ -2,30 +2,47 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use chainbridge as bridge;
+use codec::Encode;
 use frame_support::{decl_module, decl_storage, decl_event, decl_error, ensure, StorageMap};
-use frame_support::traits::{EnsureOrigin};
+use frame_support::{Blake2_128Concat, StorageHasher};
+use frame_support::storage::migration::{StorageIterator, take_storage_value};
+use frame_support::traits::{EnsureOrigin, Get};
+use frame_support::weights::Weight;
 use frame_system::{ensure_signed};
 use sp_std::prelude::*;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+mod default_weights;
 mod mock;
 mod tests;
 
+pub trait WeightInfo {
+  fn write_msg(m: u32) -> Weight;
+  fn send_msg(m: u32) -> Weight;
+}
+
 // Configuration
 pub trait Trait: frame_system::Trait + bridge::Trait {
   type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
   type BridgeOrigin: EnsureOrigin<Self::Origin, Success = Self::AccountId>;
+
+  /// Resource ID of outbound messages, registered with the bridge as a generic resource
+  type MessageId: Get<bridge::ResourceId>;
+
+  /// Weight information for extrinsics in this pallet
+  type WeightInfo: WeightInfo;
 }
 
 pub type ExtAddress = Vec<u8>;
 pub type Message = Vec<u8>;
-pub type ChainId = u8;
 pub type Nonce = u64;
 
 // Storage
 decl_storage! {
   trait Store for Module<T: Trait> as SimpleMessageStorage {
     Inbox get(fn inbox): map hasher(blake2_128_concat) Nonce => (T::AccountId, T::BlockNumber, Message);
-    DestinationNonce get(fn nonce): map hasher(blake2_128_concat) ChainId => Nonce;
+    DestinationNonce get(fn nonce): map hasher(blake2_128_concat) bridge::ChainIdOf<T> => Nonce;
   }
 }
 
@@ -33,6 +50,7 @@ decl_storage! {
 decl_event! {
   pub enum Event<T> where
     AccountId = <T as frame_system::Trait>::AccountId,
+    ChainId = bridge::ChainIdOf<T>,
   {
     SimpleMessageTransfer(ExtAddress, Message),
     MessageReceived(AccountId, Message, ChainId, Nonce),
@@ -52,8 +70,12 @@ decl_module! {
   pub struct Module<T: Trait> for enum Call where origin: T::Origin {
     fn deposit_event() = default;
 
+    fn on_runtime_upgrade() -> Weight {
+      Self::widen_destination_nonces()
+    }
+
     /// Write a message to chain
-    #[weight = 10_000]
+    #[weight = <T as Trait>::WeightInfo::write_msg(msg.len() as u32)]
     fn write_msg(origin, from: ExtAddress, nonce: Nonce, msg: Message) {
       let sender = T::BridgeOrigin::ensure_origin(origin)?;
       ensure!(!Inbox::<T>::contains_key(nonce), Error::<T>::MessageAlreadyExists);
@@ -63,15 +85,41 @@ decl_module! {
       Self::deposit_event(RawEvent::SimpleMessageTransfer(from, msg));
     }
 
-    /// Write a message to chain
-    #[weight = 10_000]
-    fn send_msg(origin, data: Message, dest_id: ChainId) {
+    /// Send a message to a (whitelisted) destination chain, as a generic transfer of the bridge
+    #[weight = <T as Trait>::WeightInfo::send_msg(data.len() as u32)]
+    fn send_msg(origin, data: Message, dest_id: bridge::ChainIdOf<T>) {
       let source = ensure_signed(origin)?;
       ensure!(<bridge::Module<T>>::chain_whitelisted(dest_id), Error::<T>::InvalidDestination);
+      <bridge::Module<T>>::transfer_generic(&source, dest_id, T::MessageId::get(), data.clone())?;
 
       let nonce = Self::nonce(dest_id);
-      DestinationNonce::insert(dest_id, nonce + 1);
+      DestinationNonce::<T>::insert(dest_id, nonce + 1);
       Self::deposit_event(RawEvent::MessageReceived(source, data, dest_id, nonce));
     }
   }
 }
+
+impl<T: Trait> Module<T> {
+  /// Re-keys the nonces stored with `u8` chain IDs when the bridge uses wider chain IDs. The
+  /// map only holds an entry per destination chain, so it's checked on every upgrade.
+  fn widen_destination_nonces() -> Weight {
+    if bridge::ChainIdOf::<T>::default().encoded_size() == 1 {
+      return 0;
+    }
+
+    let entries: Vec<(Vec<u8>, Nonce)> =
+      StorageIterator::new(b"SimpleMessageStorage", b"DestinationNonce").collect();
+    let mut widened = 0u64;
+    for (key, _) in &entries {
+      // A `u8` key is the 16 byte hash of the chain ID followed by the chain ID
+      if key.len() != 17 || Blake2_128Concat::hash(&key[16..]) != key[..] {
+        continue;
+      }
+      if let Some(nonce) = take_storage_value::<Nonce>(b"SimpleMessageStorage", b"DestinationNonce", key) {
+        DestinationNonce::<T>::insert(bridge::ChainIdOf::<T>::from(key[16]), nonce);
+        widened += 1;
+      }
+    }
+    T::DbWeight::get().reads_writes(entries.len() as u64, widened.saturating_mul(2))
+  }
+}
@@ -2,9 +2,13 @@
 
 use super::*;
 
-use frame_support::{ord_parameter_types, parameter_types, weights::Weight};
+use frame_support::{
+  assert_ok, ord_parameter_types, parameter_types,
+  traits::{OnFinalize, OnInitialize},
+  weights::Weight,
+};
 use frame_system::{self as system};
-use sp_core::H256;
+use sp_core::{sr25519, H256};
 use sp_runtime::{
   testing::Header,
   traits::{AccountIdConversion, BlakeTwo256, Block as BlockT, IdentityLookup},
@@ -75,8 +79,19 @@ impl pallet_balances::Trait for Test {
 }
 
 parameter_types! {
-  pub const TestChainId: u8 = 5;
+  pub const TestChainId: u32 = 5;
+  pub const TestChainIdFormat: bridge::ChainIdFormat = bridge::ChainIdFormat::Narrow;
   pub const ProposalLifetime: u64 = 100;
+  pub const ProposalRetention: u64 = 100;
+  pub const MaxPrunedPerBlock: u32 = 10;
+  pub const StorePreimages: bool = true;
+  pub const MaxVotesPerBatch: u32 = 10;
+  pub const DepositRetention: u64 = 100;
+  pub const ProposalReward: u64 = 10;
+  pub const RelayerBond: u64 = 0;
+  pub const UnbondingDelay: u64 = 10;
+  pub const SlashFraction: Perbill = Perbill::from_percent(10);
+  pub const AdminDelay: u64 = 0;
 }
 
 pub type Randomness = pallet_randomness_collective_flip::Module<Test>;
@@ -84,26 +99,52 @@ pub type Randomness = pallet_randomness_collective_flip::Module<Test>;
 parameter_types! {
   pub const MaxActiveRelayers: u8 = 3;
   pub const EpochDuration: u8 = 10;
+  pub const VrfResultLifetime: u32 = 2;
 }
 
 impl dorr::Trait for Test {
     type Event = Event;
     type MaxActiveRelayers = MaxActiveRelayers;
     type EpochDuration = EpochDuration;
+    type VrfResultLifetime = VrfResultLifetime;
     type RandomnessSource = Randomness;
+    type EligibleCandidates = Bridge;
+    type WeightInfo = ();
 }
 
 impl bridge::Trait for Test {
   type Event = Event;
   type AdminOrigin = frame_system::EnsureRoot<Self::AccountId>;
+  type VetoOrigin = frame_system::EnsureRoot<Self::AccountId>;
+  type AdminDelay = AdminDelay;
   type Proposal = Call;
-  type ChainId = TestChainId;
+  type ChainId = u32;
+  type ChainIdentity = TestChainId;
+  type ChainIdFormat = TestChainIdFormat;
   type ProposalLifetime = ProposalLifetime;
+  type ProposalRetention = ProposalRetention;
+  type MaxPrunedPerBlock = MaxPrunedPerBlock;
+  type StorePreimages = StorePreimages;
+  type MaxVotesPerBatch = MaxVotesPerBatch;
+  type DepositRetention = DepositRetention;
+  type Currency = Balances;
+  type ProposalReward = ProposalReward;
+  type RelayerBond = RelayerBond;
+  type UnbondingDelay = UnbondingDelay;
+  type SlashFraction = SlashFraction;
+  type OnSlash = ();
+  type WeightInfo = ();
+}
+
+parameter_types! {
+  pub MessageId: bridge::ResourceId = bridge::derive_resource_id(1, b"message");
 }
 
 impl Trait for Test {
   type Event = Event;
   type BridgeOrigin = bridge::EnsureBridge<Test>;
+  type MessageId = MessageId;
+  type WeightInfo = ();
 }
 
 pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
@@ -137,3 +178,41 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
   ext.execute_with(|| System::set_block_number(1));
   ext
 }
+
+pub fn run_to_block(n: u64) {
+  while System::block_number() < n {
+    Dorr::on_finalize(System::block_number());
+    System::on_finalize(System::block_number());
+    System::set_block_number(System::block_number() + 1);
+    System::on_initialize(System::block_number());
+    Dorr::on_initialize(System::block_number());
+  }
+}
+
+// Runs to the first block of the next epoch
+fn run_to_next_epoch() {
+  let epoch_duration = EpochDuration::get() as u64;
+  run_to_block(Dorr::get_current_epoch() * epoch_duration + 1);
+}
+
+// Registers the DORR key of a relayer and submits its VRF result, so that it's active from the
+// epoch the result is elected for
+pub fn activate_relayer(relayer: u64) {
+  let keypair = schnorrkel::MiniSecretKey::from_bytes(&[relayer as u8; 32])
+    .unwrap()
+    .expand_to_keypair(schnorrkel::ExpansionMode::Ed25519);
+  let payload = Dorr::pk_ownership_payload(&relayer, Dorr::get_current_epoch());
+  let signature = sr25519::Signature::from_raw(keypair.sign_simple(b"substrate", &payload).to_bytes());
+  assert_ok!(Dorr::set_pk(Origin::signed(relayer), keypair.public.to_bytes().to_vec(), signature));
+
+  run_to_next_epoch();
+  let randomness = Dorr::get_public_randomness(Dorr::get_current_epoch());
+  let context = schnorrkel::signing_context(&Vec::<u8>::new());
+  let (inout, proof, _) = keypair.vrf_sign(context.bytes(randomness.as_ref()));
+  assert_ok!(Dorr::set_vrf_results(
+    Origin::signed(relayer),
+    inout.to_preout().to_bytes().to_vec(),
+    proof.to_bytes().to_vec()
+  ));
+  run_to_next_epoch();
+}
@@ -1,8 +1,21 @@
 #![cfg(test)]
 
 use super::*;
+use codec::Encode;
 use frame_support::{assert_ok};
-use super::mock::{new_test_ext, Origin, Call, Bridge, SimpleMsg, RELAYER_A};
+use super::mock::{activate_relayer, new_test_ext, Origin, Call, Bridge, MessageId, SimpleMsg, Test, RELAYER_A};
+use frame_support::assert_noop;
+
+fn register_message_resource() {
+  let resource = bridge::Resource {
+    method: b"SimpleMsg.send_msg".to_vec(),
+    kind: bridge::ResourceKind::Generic,
+    destinations: vec![],
+    enabled: true,
+    call_index: [0, 0],
+  };
+  assert_ok!(Bridge::set_resource(Origin::root(), MessageId::get(), resource));
+}
 
 #[test]
 fn writeing_incoming_msg_should_work() {
@@ -12,20 +25,28 @@ fn writeing_incoming_msg_should_work() {
 
     let proposal = Call::SimpleMsg(crate::Call::write_msg(from, 0, msg));
     let prop_id = 1;
-    let src_id = 1;
-    let r_id = bridge::derive_resource_id(src_id, b"hash");
-    let resource = b"SimpleMsg.write_msg".to_vec();
+    let src_id: u32 = 1;
+    let r_id = bridge::derive_resource_id(src_id as u8, b"hash");
+    let call = proposal.encode();
+    let resource = bridge::Resource {
+      method: b"SimpleMsg.write_msg".to_vec(),
+      kind: bridge::ResourceKind::Generic,
+      destinations: vec![],
+      enabled: true,
+      call_index: [call[0], call[1]],
+    };
 
     assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_A));
     assert_ok!(Bridge::whitelist_chain(Origin::root(), src_id));
     assert_ok!(Bridge::set_resource(Origin::root(), r_id, resource));
+    activate_relayer(RELAYER_A);
 
     assert_ok!(Bridge::acknowledge_proposal(
       Origin::signed(RELAYER_A),
       prop_id,
       src_id,
       r_id,
-      Box::new(proposal.clone())
+      bridge::ProposalOrHash::Proposal(Box::new(proposal.clone()))
     ));
   });
 }
@@ -37,11 +58,58 @@ fn send_msg_should_work() {
     let dest_id = 1;
 
     assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_id));
+    register_message_resource();
 
     assert_ok!(SimpleMsg::send_msg(
       Origin::signed(0x02),
-      msg,
+      msg.clone(),
       dest_id,
     ));
+    assert_eq!(SimpleMsg::nonce(dest_id), 1);
+    assert_eq!(
+      Bridge::deposit_record(dest_id, 1).map(|record| record.payload),
+      Some(bridge::DepositPayload::Generic(msg))
+    );
   });
 }
+
+#[test]
+fn send_msg_respects_bridge_pause() {
+  new_test_ext().execute_with(|| {
+    let dest_id = 1;
+    let outbound = bridge::PauseScope::Direction(bridge::BridgeDirection::Outbound);
+
+    assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_id));
+    register_message_resource();
+    assert_ok!(Bridge::pause(Origin::root(), outbound.clone()));
+
+    assert_noop!(
+      SimpleMsg::send_msg(Origin::signed(RELAYER_A), vec![1], dest_id),
+      bridge::Error::<Test>::DirectionPaused
+    );
+
+    assert_ok!(Bridge::unpause(Origin::root(), outbound));
+    assert_ok!(SimpleMsg::send_msg(Origin::signed(RELAYER_A), vec![1], dest_id));
+    assert_eq!(SimpleMsg::nonce(dest_id), 1);
+  });
+}
+
+#[test]
+fn widen_destination_nonces() {
+  use frame_support::storage::migration::put_storage_value;
+  use frame_support::traits::OnRuntimeUpgrade;
+
+  new_test_ext().execute_with(|| {
+    // Nonce stored with a `u8` chain ID
+    let key = Blake2_128Concat::hash(&1u8.encode());
+    put_storage_value(b"SimpleMessageStorage", b"DestinationNonce", &key, 4 as Nonce);
+
+    SimpleMsg::on_runtime_upgrade();
+    assert_eq!(SimpleMsg::nonce(1), 4);
+
+    // Nonces stored with the wide chain IDs are kept
+    DestinationNonce::<Test>::insert(1, 5);
+    SimpleMsg::on_runtime_upgrade();
+    assert_eq!(SimpleMsg::nonce(1), 5);
+  })
+}